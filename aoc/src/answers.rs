@@ -0,0 +1,197 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use crate::runner::{self, Outcome, Status};
+use crate::solution::{Answer, Part};
+
+const ANSWERS_PATH: &str = "answers.txt";
+
+/**
+Known answers, one per line in the form `<day> <part> <answer>`. Blank lines and lines starting
+with `#` are ignored.
+*/
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Answers {
+    answers: HashMap<(usize, Part), Answer>,
+}
+
+impl Answers {
+    /// Load the answers file from the root of the crate
+    pub fn load_default() -> Result<Self, String> {
+        Self::load(runner::crate_path(ANSWERS_PATH))
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)
+            .map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+
+        Self::parse(&contents).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        let mut answers = HashMap::new();
+
+        for (i, line) in contents.lines().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split_whitespace().collect();
+
+            if fields.len() != 3 {
+                return Err(format!(
+                    "line {}: expected '<day> <part> <answer>', found '{}'",
+                    i + 1,
+                    line
+                ));
+            }
+
+            let day = fields[0]
+                .parse()
+                .map_err(|_| format!("line {}: invalid day '{}'", i + 1, fields[0]))?;
+            let part = Part::try_from(fields[1]).map_err(|e| format!("line {}: {}", i + 1, e))?;
+
+            if answers.insert((day, part), fields[2].to_owned()).is_some() {
+                return Err(format!(
+                    "line {}: duplicate answer for day {} part {}",
+                    i + 1,
+                    day,
+                    part
+                ));
+            }
+        }
+
+        Ok(Self { answers })
+    }
+
+    pub fn get(&self, day: usize, part: Part) -> Option<&Answer> {
+        self.answers.get(&(day, part))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Verdict {
+    Pass,
+    Fail {
+        expected: Answer,
+    },
+    /// There is no known answer to check against
+    Missing,
+    /// The day couldn't be run, usually because the input is missing
+    Skipped,
+}
+
+/**
+Check an outcome against the known answers. Returns `None` for parts that have neither a solution
+nor a known answer, since there is nothing to verify.
+*/
+pub fn check(outcome: &Outcome, answers: &Answers) -> Option<Verdict> {
+    let expected = answers.get(outcome.day, outcome.part);
+
+    let verdict = match (&outcome.status, expected) {
+        (Status::Skipped, _) => Verdict::Skipped,
+        (Status::Solved(_), None) => Verdict::Missing,
+        (Status::Solved(answer), Some(expected)) if answer == expected => Verdict::Pass,
+        (_, Some(expected)) => Verdict::Fail {
+            expected: expected.clone(),
+        },
+        (Status::Unsolved, None) => return None,
+    };
+
+    Some(verdict)
+}
+
+/// Run every day and check the results against the known answers
+pub fn verify(answers: &Answers) -> Vec<(Outcome, Verdict)> {
+    runner::run_all()
+        .into_iter()
+        .filter_map(|outcome| check(&outcome, answers).map(|verdict| (outcome, verdict)))
+        .collect()
+}
+
+pub fn any_failed(checks: &[(Outcome, Verdict)]) -> bool {
+    checks
+        .iter()
+        .any(|(_, verdict)| matches!(verdict, Verdict::Fail { .. }))
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::{check, Answers, Verdict};
+    use crate::runner::{Outcome, Status};
+    use crate::solution::Part;
+
+    const ANSWERS: &str = "\
+# day part answer
+1 1 11
+1 2 31
+
+2 1 2
+";
+
+    fn outcome(day: usize, part: Part, status: Status) -> Outcome {
+        Outcome {
+            day,
+            part,
+            status,
+            parse_time: Duration::ZERO,
+            solve_time: Duration::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let answers = Answers::parse(ANSWERS).unwrap();
+
+        assert_eq!(answers.get(1, Part::One), Some(&"11".to_owned()));
+        assert_eq!(answers.get(1, Part::Two), Some(&"31".to_owned()));
+        assert_eq!(answers.get(2, Part::One), Some(&"2".to_owned()));
+        assert_eq!(answers.get(2, Part::Two), None);
+
+        assert!(Answers::parse("1 1").is_err());
+        assert!(Answers::parse("x 1 11").is_err());
+        assert!(Answers::parse("1 3 11").is_err());
+        assert!(Answers::parse("1 1 11\n1 1 12").is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        let answers = Answers::parse(ANSWERS).unwrap();
+
+        let solved =
+            |day, part, answer: &str| outcome(day, part, Status::Solved(answer.to_owned()));
+
+        assert_eq!(
+            check(&solved(1, Part::One, "11"), &answers),
+            Some(Verdict::Pass)
+        );
+        assert_eq!(
+            check(&solved(1, Part::Two, "30"), &answers),
+            Some(Verdict::Fail {
+                expected: "31".to_owned()
+            })
+        );
+        assert_eq!(
+            check(&solved(2, Part::Two, "4"), &answers),
+            Some(Verdict::Missing)
+        );
+        assert_eq!(
+            check(&outcome(2, Part::One, Status::Unsolved), &answers),
+            Some(Verdict::Fail {
+                expected: "2".to_owned()
+            })
+        );
+        assert_eq!(
+            check(&outcome(2, Part::Two, Status::Unsolved), &answers),
+            None
+        );
+        assert_eq!(
+            check(&outcome(1, Part::One, Status::Skipped), &answers),
+            Some(Verdict::Skipped)
+        );
+    }
+}
@@ -0,0 +1,185 @@
+use std::{fmt::Display, time::Duration};
+
+use crate::registry::Day;
+use crate::runner;
+use crate::solution::{self, Part};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// Summarise a set of timings. Returns `None` if there are no timings.
+    pub fn from(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let n = sorted.len();
+
+        let median = if n.is_multiple_of(2) {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        } else {
+            sorted[n / 2]
+        };
+
+        let mean = sorted.iter().sum::<Duration>() / n as u32;
+
+        let variance = sorted
+            .iter()
+            .map(|sample| (sample.as_secs_f64() - mean.as_secs_f64()).powi(2))
+            .sum::<f64>()
+            / n as f64;
+
+        Some(Self {
+            min: sorted[0],
+            median,
+            mean,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "min {:>10.2?}  median {:>10.2?}  mean {:>10.2?}  stddev {:>10.2?}",
+            self.min, self.median, self.mean, self.stddev
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Report {
+    pub parse: Stats,
+    pub solve: Stats,
+    pub total: Stats,
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "parse  {}", self.parse)?;
+        writeln!(f, "solve  {}", self.solve)?;
+        writeln!(f, "total  {}", self.total)
+    }
+}
+
+/**
+Run a single part of a day repeatedly, discarding the first `warmup` runs, and summarise the time
+taken to parse the input and to solve the puzzle. Solutions' debug output is turned off while the
+runs are going.
+*/
+pub fn bench(
+    day: &Day,
+    part: Part,
+    lines: &[String],
+    warmup: usize,
+    runs: usize,
+) -> Result<Report, String> {
+    if runs == 0 {
+        return Err("the number of runs must be at least 1".to_owned());
+    }
+
+    if !day.has_part(part) {
+        return Err(format!(
+            "day {} has no solution for part {}",
+            day.number, part
+        ));
+    }
+
+    let mut parse_times = vec![];
+    let mut solve_times = vec![];
+
+    let debug_output = solution::debug_output();
+    solution::set_debug_output(false);
+
+    for i in 0..warmup + runs {
+        let run = day.run(part, lines.to_vec());
+
+        if i >= warmup {
+            parse_times.push(run.parse_time);
+            solve_times.push(run.solve_time);
+        }
+    }
+
+    solution::set_debug_output(debug_output);
+
+    let total_times: Vec<Duration> = parse_times
+        .iter()
+        .zip(&solve_times)
+        .map(|(parse, solve)| *parse + *solve)
+        .collect();
+
+    // There is at least one run so the stats are always available
+    Ok(Report {
+        parse: Stats::from(&parse_times).unwrap(),
+        solve: Stats::from(&solve_times).unwrap(),
+        total: Stats::from(&total_times).unwrap(),
+    })
+}
+
+/**
+Benchmark a single day. If no part is given, every part the day has a solution for is
+benchmarked.
+*/
+pub fn bench_day(
+    day: usize,
+    part: Option<Part>,
+    input: Option<&str>,
+    warmup: usize,
+    runs: usize,
+) -> Result<Vec<(Part, Report)>, String> {
+    let (day, lines) = runner::load_day(day, input)?;
+
+    let parts = match part {
+        Some(part) => vec![part],
+        None => day.parts(),
+    };
+
+    parts
+        .into_iter()
+        .map(|part| Ok((part, bench(day, part, &lines, warmup, runs)?)))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::Stats;
+
+    #[test]
+    fn test_stats() {
+        let samples: Vec<Duration> = [4, 2, 8, 6]
+            .iter()
+            .map(|&ms| Duration::from_millis(ms))
+            .collect();
+
+        let stats = Stats::from(&samples).unwrap();
+
+        assert_eq!(stats.min, Duration::from_millis(2));
+        assert_eq!(stats.median, Duration::from_millis(5));
+        assert_eq!(stats.mean, Duration::from_millis(5));
+
+        // Population standard deviation of 2, 4, 6, 8 is sqrt(5)
+        assert!((stats.stddev.as_secs_f64() - 5f64.sqrt() / 1000.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_stats_odd_and_empty() {
+        let samples: Vec<Duration> = [3, 1, 2].iter().map(|&s| Duration::from_secs(s)).collect();
+
+        let stats = Stats::from(&samples).unwrap();
+
+        assert_eq!(stats.median, Duration::from_secs(2));
+        assert!(Stats::from(&[]).is_none());
+    }
+}
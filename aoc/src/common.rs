@@ -1,7 +1,19 @@
+pub mod bits;
+pub mod pattern;
+pub mod regions;
+pub mod search;
+pub mod sparse;
+pub mod transform;
+
 use std::cmp::Ordering;
 use std::fmt::{Debug, Display, Write};
-use std::ops::{Add, Div, Index, IndexMut, Mul, Sub};
+use std::ops::{Add, AddAssign, Div, Index, IndexMut, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+use bits::BitGrid;
+use regions::Connectivity;
 
+/// A cell's contents together with its position in the grid
 #[derive(Debug, Clone, Copy, Eq)]
 pub struct Cell<T> {
     pub x: usize,
@@ -10,8 +22,8 @@ pub struct Cell<T> {
 }
 
 impl<T> Cell<T> {
-    fn position(&self) -> Vector2di {
-        Vector2di::new(self.x as i64, self.y as i64)
+    pub fn position(&self) -> Vector2di {
+        (self.x, self.y).into()
     }
 }
 
@@ -21,9 +33,9 @@ impl<T> PartialEq for Cell<T> {
     }
 }
 
-impl<T> Into<Vector2di> for &Cell<T> {
-    fn into(self) -> Vector2di {
-        (self.x, self.y).into()
+impl<T> From<&Cell<T>> for Vector2di {
+    fn from(value: &Cell<T>) -> Self {
+        (value.x, value.y).into()
     }
 }
 
@@ -40,49 +52,184 @@ impl<T: Eq> PartialOrd for Cell<T> {
     }
 }
 
-#[derive(Debug, Clone)]
+/**
+Contents of a cell that can be built from a character of puzzle input. The position of the
+character is provided for contents that need to know where they are.
+*/
+pub trait CellContents {
+    fn from(x: usize, y: usize, c: &char) -> Self;
+}
+
+/// The result of a bounds-checked lookup in a grid
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CellType<'a, T> {
+    Cell(&'a T),
+    OutOfBounds,
+}
+
+/**
+A rectangular grid stored row by row in a single `Vec`. Positions are only stored as the index
+into the `Vec`, and are worked out again when they are needed.
+*/
+#[derive(Debug, Clone, PartialEq)]
 pub struct Grid<T> {
-    dim: usize,
-    cells: Vec<Vec<Cell<T>>>,
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+    topology: Topology,
 }
 
+/// What lies past the edges of a grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Topology {
+    /// Nothing, positions outside the grid don't exist
+    #[default]
+    Bounded,
+    /**
+    The opposite edge. Every position is treated as the position inside the grid it lands on when
+    the grid is repeated in every direction, so the grid behaves like the surface of a torus.
+    */
+    Wrapping,
+}
+
+/**
+A pair of numbers, used for grid positions and the steps between them. Arithmetic uses the
+operators of the component type, so integer overflow panics in debug builds just as it would for
+the components on their own.
+*/
 #[derive(Debug, Copy, Clone, PartialEq, Default, Eq, Hash)]
-pub struct Vector2di {
-    pub x: i64,
-    pub y: i64,
+pub struct Vector2<N> {
+    pub x: N,
+    pub y: N,
 }
 
-impl Vector2di {
-    pub fn new(x: i64, y: i64) -> Self {
+/// The vector used for grid positions, which can go negative when stepping off the grid
+pub type Vector2di = Vector2<i64>;
+
+impl<N> Vector2<N> {
+    pub fn new(x: N, y: N) -> Self {
         Self { x, y }
     }
+
+    /// Convert each component to a type that can hold every value of this one, like `i32` to `i64`
+    pub fn cast<M: From<N>>(self) -> Vector2<M> {
+        Vector2::new(self.x.into(), self.y.into())
+    }
+
+    /// Convert each component to another type, failing if either is out of range for it
+    pub fn try_cast<M: TryFrom<N>>(self) -> Result<Vector2<M>, String>
+    where
+        N: Copy + Display,
+    {
+        match (M::try_from(self.x), M::try_from(self.y)) {
+            (Ok(x), Ok(y)) => Ok(Vector2::new(x, y)),
+            _ => Err(format!(
+                "({}, {}) is out of range for {}",
+                self.x,
+                self.y,
+                std::any::type_name::<M>()
+            )),
+        }
+    }
 }
 
-impl From<(i32, i32)> for Vector2di {
-    fn from(value: (i32, i32)) -> Self {
-        Vector2di::from((value.0 as i64, value.1 as i64))
+impl<N: Copy + Add<Output = N> + Sub<Output = N> + Mul<Output = N>> Vector2<N> {
+    pub fn dot(&self, other: Self) -> N {
+        self.x * other.x + self.y * other.y
+    }
+
+    /**
+    The z component of the cross product, which is also the determinant of the two vectors as the
+    columns of a matrix. It's positive when `other` is clockwise from `self` on the grid, since y
+    points down.
+    */
+    pub fn cross(&self, other: Self) -> N {
+        self.x * other.y - self.y * other.x
     }
 }
 
-impl From<(i64, i64)> for Vector2di {
-    fn from(value: (i64, i64)) -> Self {
-        Self {
-            x: value.0,
-            y: value.1,
+impl Vector2di {
+    /// The number of orthogonal steps between two positions
+    pub fn manhattan_distance(&self, other: Vector2di) -> usize {
+        ((self.x - other.x).unsigned_abs() + (self.y - other.y).unsigned_abs()) as usize
+    }
+
+    /// The number of steps between two positions when diagonal steps are allowed
+    pub fn chebyshev_distance(&self, other: Vector2di) -> usize {
+        (self.x - other.x)
+            .unsigned_abs()
+            .max((self.y - other.y).unsigned_abs()) as usize
+    }
+
+    /// Rotate 90 degrees anticlockwise on the grid, so up becomes left
+    pub fn rotate_left(&self) -> Self {
+        Self::new(self.y, -self.x)
+    }
+
+    /// Rotate 90 degrees clockwise on the grid, so up becomes right
+    pub fn rotate_right(&self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+
+    /**
+    The smallest whole step in the same direction, found by dividing both components by their
+    greatest common divisor. Stepping by it visits every grid position on the line, where stepping
+    by `self` could skip some. The zero vector is left as it is.
+    */
+    pub fn reduced(&self) -> Self {
+        let mut a = self.x.unsigned_abs();
+        let mut b = self.y.unsigned_abs();
+
+        while b != 0 {
+            (a, b) = (b, a % b);
         }
+
+        if a == 0 {
+            return *self;
+        }
+
+        Self::new(self.x / a as i64, self.y / a as i64)
+    }
+
+    /// Component-wise `rem_euclid`, wrapping a position into the rectangle from the origin to `bounds`
+    pub fn rem_euclid(&self, bounds: Vector2di) -> Self {
+        Self::new(self.x.rem_euclid(bounds.x), self.y.rem_euclid(bounds.y))
+    }
+}
+
+impl TryFrom<Vector2di> for (usize, usize) {
+    type Error = String;
+
+    fn try_from(value: Vector2di) -> Result<Self, Self::Error> {
+        value.try_cast::<usize>().map(Into::into)
+    }
+}
+
+impl<N> From<(N, N)> for Vector2<N> {
+    fn from(value: (N, N)) -> Self {
+        Self::new(value.0, value.1)
+    }
+}
+
+impl<N> From<Vector2<N>> for (N, N) {
+    fn from(value: Vector2<N>) -> Self {
+        (value.x, value.y)
+    }
+}
+
+impl From<(i32, i32)> for Vector2di {
+    fn from(value: (i32, i32)) -> Self {
+        Vector2::new(value.0, value.1).cast()
     }
 }
 
 impl From<(usize, usize)> for Vector2di {
     fn from(value: (usize, usize)) -> Self {
-        Self {
-            x: value.0 as i64,
-            y: value.1 as i64,
-        }
+        Vector2::new(value.0, value.1).try_cast().unwrap()
     }
 }
 
-impl Add for Vector2di {
+impl<N: Add<Output = N>> Add for Vector2<N> {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
@@ -93,7 +240,7 @@ impl Add for Vector2di {
     }
 }
 
-impl Sub for Vector2di {
+impl<N: Sub<Output = N>> Sub for Vector2<N> {
     type Output = Self;
 
     fn sub(self, rhs: Self) -> Self::Output {
@@ -104,10 +251,35 @@ impl Sub for Vector2di {
     }
 }
 
-impl Mul<i64> for Vector2di {
+impl<N: AddAssign> AddAssign for Vector2<N> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl<N: SubAssign> SubAssign for Vector2<N> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+impl<N: Neg<Output = N>> Neg for Vector2<N> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
+impl<N: Copy + Mul<Output = N>> Mul<N> for Vector2<N> {
     type Output = Self;
 
-    fn mul(self, rhs: i64) -> Self::Output {
+    fn mul(self, rhs: N) -> Self::Output {
         Self {
             x: self.x * rhs,
             y: self.y * rhs,
@@ -119,14 +291,14 @@ impl Mul<usize> for Vector2di {
     type Output = Self;
 
     fn mul(self, rhs: usize) -> Self::Output {
-        self * rhs as i64
+        self * i64::try_from(rhs).unwrap()
     }
 }
 
-impl Div<Vector2di> for Vector2di {
+impl<N: Div<Output = N>> Div for Vector2<N> {
     type Output = Self;
 
-    fn div(self, rhs: Vector2di) -> Self::Output {
+    fn div(self, rhs: Self) -> Self::Output {
         Self {
             x: self.x / rhs.x,
             y: self.y / rhs.y,
@@ -134,7 +306,7 @@ impl Div<Vector2di> for Vector2di {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum OrthoDirection {
     #[default]
     Up,
@@ -170,9 +342,9 @@ impl OrthoDirection {
     }
 }
 
-impl Into<Vector2di> for OrthoDirection {
-    fn into(self) -> Vector2di {
-        match self {
+impl From<OrthoDirection> for Vector2di {
+    fn from(value: OrthoDirection) -> Self {
+        match value {
             OrthoDirection::Up => Vector2di::new(0, -1),
             OrthoDirection::Right => Vector2di::new(1, 0),
             OrthoDirection::Down => Vector2di::new(0, 1),
@@ -181,6 +353,87 @@ impl Into<Vector2di> for OrthoDirection {
     }
 }
 
+/// One of the eight compass directions, with north pointing up the grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Direction8 {
+    #[default]
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Direction8 {
+    /// Every direction, clockwise from north
+    pub const ALL: [Direction8; 8] = [
+        Self::N,
+        Self::NE,
+        Self::E,
+        Self::SE,
+        Self::S,
+        Self::SW,
+        Self::W,
+        Self::NW,
+    ];
+
+    /// Rotate clockwise by 45 degrees for each step. Negative steps rotate anticlockwise.
+    pub fn rotate(&self, steps: i32) -> Self {
+        let index = Self::ALL.iter().position(|d| d == self).unwrap() as i32;
+
+        Self::ALL[(index + steps).rem_euclid(8) as usize]
+    }
+
+    pub fn left_45(&self) -> Self {
+        self.rotate(-1)
+    }
+
+    pub fn right_45(&self) -> Self {
+        self.rotate(1)
+    }
+
+    pub fn left(&self) -> Self {
+        self.rotate(-2)
+    }
+
+    pub fn right(&self) -> Self {
+        self.rotate(2)
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        matches!(self, Self::NE | Self::SE | Self::SW | Self::NW)
+    }
+}
+
+impl From<OrthoDirection> for Direction8 {
+    fn from(value: OrthoDirection) -> Self {
+        match value {
+            OrthoDirection::Up => Direction8::N,
+            OrthoDirection::Right => Direction8::E,
+            OrthoDirection::Down => Direction8::S,
+            OrthoDirection::Left => Direction8::W,
+        }
+    }
+}
+
+impl From<Direction8> for Vector2di {
+    fn from(value: Direction8) -> Self {
+        match value {
+            Direction8::N => Vector2di::new(0, -1),
+            Direction8::NE => Vector2di::new(1, -1),
+            Direction8::E => Vector2di::new(1, 0),
+            Direction8::SE => Vector2di::new(1, 1),
+            Direction8::S => Vector2di::new(0, 1),
+            Direction8::SW => Vector2di::new(-1, 1),
+            Direction8::W => Vector2di::new(-1, 0),
+            Direction8::NW => Vector2di::new(-1, -1),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub enum WindingMode {
     #[default]
@@ -195,234 +448,439 @@ pub struct NeighbourMode {
 }
 
 impl<T> Grid<T> {
-    pub fn get(&self, loc: impl Into<Vector2di>) -> Option<&Cell<T>> {
+    /// A grid where every cell starts with the same contents
+    pub fn filled(width: usize, height: usize, contents: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![contents; width * height],
+            topology: Topology::Bounded,
+        }
+    }
+
+    /// The same grid, with positions past its edges treated according to `topology`
+    pub fn with_topology(mut self, topology: Topology) -> Self {
+        self.topology = topology;
+        self
+    }
+
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    /**
+    The position inside the grid that `loc` refers to. For a bounded grid this is `loc` itself if
+    it is inside the grid, and for a wrapping grid it is `loc` wrapped round onto the grid.
+    */
+    pub fn wrap(&self, loc: impl Into<Vector2di>) -> Option<Vector2di> {
         let idx = loc.into();
 
-        if idx.x < 0 || idx.y < 0 || idx.x >= self.dim as i64 || idx.y >= self.dim as i64 {
-            return None;
+        match self.topology {
+            Topology::Bounded => <(usize, usize)>::try_from(idx)
+                .is_ok_and(|(x, y)| x < self.width && y < self.height)
+                .then_some(idx),
+            Topology::Wrapping => {
+                (!self.cells.is_empty()).then(|| idx.rem_euclid((self.width, self.height).into()))
+            }
         }
+    }
 
-        Some(&self[idx])
+    pub fn contains(&self, loc: impl Into<Vector2di>) -> bool {
+        self.wrap(loc).is_some()
     }
 
-    pub fn get_mut(&mut self, loc: impl Into<Vector2di>) -> Option<&mut Cell<T>> {
-        let idx = loc.into();
+    fn index_of(&self, loc: impl Into<Vector2di>) -> Option<usize> {
+        let (x, y): (usize, usize) = self.wrap(loc)?.try_into().ok()?;
 
-        if idx.x < 0 || idx.y < 0 || idx.x >= self.dim as i64 || idx.y >= self.dim as i64 {
-            return None;
+        Some(y * self.width + x)
+    }
+
+    fn position_of(&self, index: usize) -> Vector2di {
+        (index % self.width, index / self.width).into()
+    }
+
+    pub fn get(&self, loc: impl Into<Vector2di>) -> Option<&T> {
+        let index = self.index_of(loc)?;
+
+        Some(&self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, loc: impl Into<Vector2di>) -> Option<&mut T> {
+        let index = self.index_of(loc)?;
+
+        Some(&mut self.cells[index])
+    }
+
+    /// Like [`Grid::get`], but with the out of bounds case made explicit
+    pub fn safe_index(&self, loc: impl Into<Vector2di>) -> CellType<'_, T> {
+        match self.get(loc) {
+            Some(contents) => CellType::Cell(contents),
+            None => CellType::OutOfBounds,
         }
+    }
 
-        Some(&mut self[idx])
+    /// Get the contents at an offset from the given position, if it is inside the grid
+    pub fn cell_from(&self, loc: impl Into<Vector2di>, offset: impl Into<Vector2di>) -> Option<&T> {
+        self.get(loc.into() + offset.into())
     }
 
-    pub fn insert(&mut self, loc: impl Into<Vector2di>, value: Cell<T>) {
+    /// Get the neighbour of the given position in a single direction, if it is inside the grid
+    pub fn ortho_neighbour(
+        &self,
+        loc: impl Into<Vector2di>,
+        direction: OrthoDirection,
+    ) -> Option<&T> {
+        self.cell_from(loc, direction)
+    }
+
+    pub fn insert(&mut self, loc: impl Into<Vector2di>, value: T) {
         self[loc.into()] = value;
     }
 
-    pub fn dim(&self) -> usize {
-        self.dim
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
     }
 
-    pub fn safe_ortho_neighbours(&self, loc: impl Into<Vector2di>) -> Vec<&Cell<T>> {
+    /// The position and contents of every cell, in row-major order
+    pub fn iter(&self) -> impl Iterator<Item = (Vector2di, &T)> + '_ {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, contents)| (self.position_of(i), contents))
+    }
+
+    /// Like [`Grid::iter`], but the contents can be modified
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Vector2di, &mut T)> + '_ {
+        let width = self.width;
+
+        self.cells
+            .iter_mut()
+            .enumerate()
+            .map(move |(i, contents)| ((i % width, i / width).into(), contents))
+    }
+
+    /// Every position in the grid, in row-major order
+    pub fn positions(&self) -> impl Iterator<Item = Vector2di> + '_ {
+        (0..self.cells.len()).map(|i| self.position_of(i))
+    }
+
+    /**
+    The position and contents of every cell in column-major order, so from top to bottom down the
+    first column, then down the second column and so on.
+    */
+    pub fn iter_column_major(&self) -> impl Iterator<Item = (Vector2di, &T)> + '_ {
+        (0..self.width).flat_map(move |x| {
+            (0..self.height).map(move |y| ((x, y).into(), &self.cells[y * self.width + x]))
+        })
+    }
+
+    /// Each row of the grid from top to bottom, as a slice running from left to right
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        (0..self.height).map(|y| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    /**
+    Each column of the grid from left to right. Columns aren't contiguous in memory, so each one is
+    an iterator running from top to bottom rather than a slice.
+    */
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> + '_ {
+        (0..self.width).map(|x| self.cells[x..].iter().step_by(self.width))
+    }
+
+    /// The orthogonal neighbours of a position that are inside the grid, after wrapping
+    pub fn safe_ortho_neighbours(&self, loc: impl Into<Vector2di>) -> Vec<(Vector2di, &T)> {
         let idx = loc.into();
 
-        let neighbours = vec![
-            self.get(idx + (0, -1).into()),
-            self.get(idx + (1, 0).into()),
-            self.get(idx + (0, 1).into()),
-            self.get(idx + (-1, 0).into()),
+        let neighbours: [Vector2di; 4] = [
+            idx + (0, -1).into(),
+            idx + (1, 0).into(),
+            idx + (0, 1).into(),
+            idx + (-1, 0).into(),
         ];
 
         neighbours
-            .iter()
-            .filter_map(|c| *c)
-            .collect::<Vec<&Cell<T>>>()
+            .into_iter()
+            .filter_map(|n| {
+                let n = self.wrap(n)?;
+
+                Some((n, &self[n]))
+            })
+            .collect()
+    }
+
+    /// All eight neighbours of a position that are inside the grid after wrapping, clockwise from north
+    pub fn neighbours(&self, loc: impl Into<Vector2di>) -> Vec<(Vector2di, &T)> {
+        let idx = loc.into();
+
+        Direction8::ALL
+            .into_iter()
+            .map(|direction| idx + direction.into())
+            .filter_map(|n| {
+                let n = self.wrap(n)?;
+
+                Some((n, &self[n]))
+            })
+            .collect()
+    }
+
+    /// Get the neighbour of the given position in one of the eight directions
+    pub fn neighbour(&self, loc: impl Into<Vector2di>, direction: Direction8) -> Option<&T> {
+        self.cell_from(loc, direction)
     }
 
-    pub fn ortho_neighbours(&self, cell: &Cell<T>, mode: NeighbourMode) -> Vec<Option<&Cell<T>>> {
+    /**
+    Walk from `start` in steps of `step`, yielding each cell passed through (starting with `start`
+    itself) until the walk leaves the grid. A zero step yields `start` once. On a wrapping grid the
+    walk never leaves, so it stops instead when it would come back round to `start`.
+    */
+    pub fn ray(&self, start: impl Into<Vector2di>, step: impl Into<Vector2di>) -> Ray<'_, T> {
+        let start = self.wrap(start);
+
+        Ray {
+            grid: self,
+            start: start.unwrap_or_default(),
+            next: start,
+            step: step.into(),
+        }
+    }
+
+    /// Read up to `length` cells along a ray, stopping early if the ray leaves the grid
+    pub fn read<B: FromIterator<T>>(
+        &self,
+        start: impl Into<Vector2di>,
+        step: impl Into<Vector2di>,
+        length: usize,
+    ) -> B
+    where
+        T: Clone,
+    {
+        self.ray(start, step)
+            .take(length)
+            .map(|(_, contents)| contents.clone())
+            .collect()
+    }
+
+    pub fn ortho_neighbours(
+        &self,
+        loc: impl Into<Vector2di>,
+        mode: NeighbourMode,
+    ) -> Vec<Option<&T>> {
+        let idx = loc.into();
         let mut result = vec![];
 
         let mut direction = mode.start_direction;
 
         for _ in 0..4 {
-            result.push(self.get(cell.position() + direction.into()));
+            result.push(self.get(idx + direction.into()));
             direction = direction.next(&mode.winding_mode);
         }
 
         result
     }
-}
-
-impl<T: Copy> Grid<T> {
-    pub fn from(input: Vec<Vec<T>>) -> Self {
-        Self {
-            dim: input.len(),
-            cells: input
-                .iter()
-                .enumerate()
-                .map(|(j, row)| {
-                    row.iter()
-                        .enumerate()
-                        .map(|(i, t)| Cell {
-                            x: i,
-                            y: j,
-                            contents: *t,
-                        })
-                        .collect()
-                })
-                .collect(),
-        }
-    }
 
     /**
     Replace the contents of the cell at the given position. Returns the original contents of the
     cell.
     */
     pub fn replace(&mut self, loc: impl Into<Vector2di>, value: T) -> Option<T> {
-        let original = self.get_mut(loc.into())?;
-        let contents = original.contents;
-
-        original.contents = value;
+        let original = self.get_mut(loc)?;
 
-        Some(contents)
+        Some(std::mem::replace(original, value))
     }
 
     /**
-    Swap the contents of the cells at the source and destination positions. If the provided
-    positions are the same, the swap is not attempted. If either of the positions are outside the
-    boundaries of the grid, the swap is not attempted.
-     */
-    pub fn swap(
-        &mut self,
-        source: impl Into<Vector2di> + Copy,
-        destination: impl Into<Vector2di> + Copy,
-    ) {
-        if source.into() == destination.into() {
-            return;
+    Swap the contents of the cells at the source and destination positions. If either of the
+    positions are outside the boundaries of the grid, the swap is not attempted.
+    */
+    pub fn swap(&mut self, source: impl Into<Vector2di>, destination: impl Into<Vector2di>) {
+        if let (Some(i), Some(j)) = (self.index_of(source), self.index_of(destination)) {
+            self.cells.swap(i, j);
         }
+    }
+}
+
+impl<T: CellContents> Grid<T> {
+    /**
+    Build a grid from rows of characters, constructing the contents of each cell in turn. Fails if
+    the rows are not all the same length.
+    */
+    pub fn new(input: Vec<Vec<char>>) -> Result<Self, String> {
+        Grid::try_from(input).map(|grid: Grid<char>| grid.parse_cells())
+    }
+}
 
-        let (t1, t2) = (self.get(source.into()), self.get(destination.into()));
+impl Grid<char> {
+    /**
+    Build a grid of characters from lines of puzzle input, one row per line. Fails if the lines are
+    not all the same length.
+    */
+    pub fn from_lines<S: AsRef<str>>(lines: &[S]) -> Result<Self, String> {
+        Self::try_from(
+            lines
+                .iter()
+                .map(|line| line.as_ref().chars().collect())
+                .collect::<Vec<Vec<char>>>(),
+        )
+    }
 
-        if t1.is_none() || t2.is_none() {
-            return;
+    /// Build the contents of every cell from its character, see [`CellContents`]
+    pub fn parse_cells<T: CellContents>(&self) -> Grid<T> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self
+                .cells
+                .iter()
+                .enumerate()
+                .map(|(i, c)| T::from(i % self.width, i / self.width, c))
+                .collect(),
+            topology: self.topology,
         }
+    }
+}
 
-        let t1 = t1.unwrap().contents;
-        let t2 = t2.unwrap().contents;
+impl FromStr for Grid<char> {
+    type Err = String;
 
-        if let Some(c1) = self.get_mut(source.into()) {
-            c1.contents = t2;
-        }
+    /// Build a grid of characters with one row per line of the text, see [`Grid::from_lines`]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_lines(&s.lines().collect::<Vec<&str>>())
+    }
+}
+
+impl<T> TryFrom<Vec<Vec<T>>> for Grid<T> {
+    type Error = String;
 
-        if let Some(c2) = self.get_mut(destination.into()) {
-            c2.contents = t1;
+    /// Build a grid from rows of cell contents. Fails if the rows are not all the same length.
+    fn try_from(value: Vec<Vec<T>>) -> Result<Self, Self::Error> {
+        let width = value.first().map_or(0, |row| row.len());
+
+        if let Some((j, row)) = value.iter().enumerate().find(|(_, row)| row.len() != width) {
+            return Err(format!(
+                "ragged grid: row {} has {} cells but row 0 has {}",
+                j,
+                row.len(),
+                width
+            ));
         }
+
+        Ok(Self {
+            width,
+            height: value.len(),
+            cells: value.into_iter().flatten().collect(),
+            topology: Topology::Bounded,
+        })
     }
 }
 
-pub type Region<'a, T> = Vec<&'a Cell<T>>;
-
-impl<'a, T: PartialEq + Copy> Grid<T> {
-    pub fn region(&'a self, loc: impl Into<Vector2di>) -> Region<'a, T> {
-        let mut result: Vec<&Cell<T>> = vec![];
+/// The cells along a straight line through a grid, see [`Grid::ray`]
+pub struct Ray<'a, T> {
+    grid: &'a Grid<T>,
+    start: Vector2di,
+    next: Option<Vector2di>,
+    step: Vector2di,
+}
 
-        if let Some(n) = self.get(loc) {
-            result.push(n);
+impl<'a, T> Ray<'a, T> {
+    /// End the ray before the first cell whose contents match `stop`
+    pub fn until(
+        self,
+        mut stop: impl FnMut(&T) -> bool,
+    ) -> impl Iterator<Item = (Vector2di, &'a T)> {
+        self.take_while(move |(_, contents)| !stop(contents))
+    }
+}
 
-            let mut buffer: Vec<&Cell<T>> = vec![n];
+impl<'a, T> Iterator for Ray<'a, T> {
+    type Item = (Vector2di, &'a T);
 
-            while !buffer.is_empty() {
-                let n = buffer.pop().unwrap();
-                let neighbours = self.safe_ortho_neighbours(n);
+    fn next(&mut self) -> Option<Self::Item> {
+        let position = self.next.take()?;
 
-                for neighbour in neighbours {
-                    if neighbour.contents == n.contents && !result.contains(&neighbour) {
-                        result.push(neighbour);
-                        buffer.push(neighbour);
-                    }
-                }
-            }
-        }
+        self.next = self
+            .grid
+            .wrap(position + self.step)
+            .filter(|&next| next != self.start);
 
-        result
+        Some((position, &self.grid[position]))
     }
+}
 
-    pub fn regions(&'a self) -> Vec<Region<'a, T>> {
-        let mut regions = vec![];
+/// The positions of a set of connected cells
+pub type Region = Vec<Vector2di>;
 
-        for cell in self.clone() {
-            if regions.iter().any(|r: &Region<'a, T>| r.contains(&&cell)) {
-                continue;
-            }
+impl<T: PartialEq> Grid<T> {
+    /// The orthogonally connected cells with the same contents as the cell at `loc`
+    pub fn region(&self, loc: impl Into<Vector2di>) -> Region {
+        let mut visited = BitGrid::new(self.width, self.height);
 
-            regions.push(self.region(&cell));
-        }
+        self.flood(loc.into(), Connectivity::Four, &|a, b| a == b, &mut visited)
+    }
+}
 
-        regions
+impl<T: Clone + PartialEq> Grid<T> {
+    /// Every orthogonally connected region of cells with the same contents, see
+    /// [`Grid::label_regions`]
+    pub fn regions(&self) -> Vec<Region> {
+        self.label_regions(Connectivity::Four)
+            .regions
+            .into_iter()
+            .map(|region| region.cells)
+            .collect()
     }
 }
 
+/// Yields the cells of a grid in row-major order, the same order as [`Grid::iter`]
 pub struct GridIntoIterator<T> {
-    grid: Grid<T>,
-    dim: usize,
-    x: usize,
-    y: usize,
+    cells: std::iter::Enumerate<std::vec::IntoIter<T>>,
+    width: usize,
 }
 
-impl<T: Copy> Iterator for GridIntoIterator<T> {
+impl<T> Iterator for GridIntoIterator<T> {
     type Item = Cell<T>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let mut x = self.x;
-        let mut y = self.y;
-
-        if x >= self.dim {
-            return None;
-        };
-
-        let result = &self.grid.cells[x][y];
-
-        y = (y + 1) % self.dim;
-
-        if y == 0 {
-            x += 1;
-
-            self.x = x;
-        }
+        let (i, contents) = self.cells.next()?;
 
-        self.y = y;
-
-        Some(*result)
+        Some(Cell {
+            x: i % self.width,
+            y: i / self.width,
+            contents,
+        })
     }
 }
 
-impl<T: Copy> IntoIterator for Grid<T> {
+impl<T> IntoIterator for Grid<T> {
     type Item = Cell<T>;
     type IntoIter = GridIntoIterator<T>;
 
     fn into_iter(self) -> Self::IntoIter {
         GridIntoIterator {
-            dim: self.dim,
-            x: 0,
-            y: 0,
-            grid: self,
+            cells: self.cells.into_iter().enumerate(),
+            width: self.width,
         }
     }
 }
 
 impl<T> Index<Vector2di> for Grid<T> {
-    type Output = Cell<T>;
+    type Output = T;
 
     fn index(&self, idx: Vector2di) -> &Self::Output {
-        let x = idx.x;
-        let y = idx.y;
+        let index = self.index_of(idx).expect("position is outside the grid");
 
-        &self.cells[y as usize][x as usize]
+        &self.cells[index]
     }
 }
 
 impl<T> Index<(i32, i32)> for Grid<T> {
-    type Output = Cell<T>;
+    type Output = T;
 
     fn index(&self, index: (i32, i32)) -> &Self::Output {
         &self[Vector2di::from(index)]
@@ -431,10 +889,9 @@ impl<T> Index<(i32, i32)> for Grid<T> {
 
 impl<T> IndexMut<Vector2di> for Grid<T> {
     fn index_mut(&mut self, idx: Vector2di) -> &mut Self::Output {
-        let x = idx.x;
-        let y = idx.y;
+        let index = self.index_of(idx).expect("position is outside the grid");
 
-        &mut self.cells[y as usize][x as usize]
+        &mut self.cells[index]
     }
 }
 
@@ -446,9 +903,9 @@ impl<T> IndexMut<(i32, i32)> for Grid<T> {
 
 impl<T: Copy + Into<char>> Display for Grid<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for j in &self.cells {
-            for i in j {
-                f.write_char(i.contents.into())?;
+        for row in self.rows() {
+            for contents in row {
+                f.write_char((*contents).into())?;
             }
 
             f.write_str("\n")?;
@@ -460,7 +917,429 @@ impl<T: Copy + Into<char>> Display for Grid<T> {
 
 #[cfg(test)]
 mod test {
-    use super::Cell;
+    use super::{
+        Cell, CellContents, CellType, Direction8, Grid, OrthoDirection, Topology, Vector2,
+        Vector2di,
+    };
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Tile {
+        c: char,
+        x: usize,
+        y: usize,
+    }
+
+    impl CellContents for Tile {
+        fn from(x: usize, y: usize, c: &char) -> Self {
+            Self { c: *c, x, y }
+        }
+    }
+
+    fn tiles() -> Grid<Tile> {
+        Grid::new(vec![vec!['a', 'b'], vec!['c', 'd']]).unwrap()
+    }
+
+    fn wide() -> Grid<char> {
+        "abc\ndef".parse().unwrap()
+    }
+
+    #[test]
+    fn test_rectangular() {
+        let grid = wide();
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get((2, 1)), Some(&'f'));
+        assert!(grid.get((2, 2)).is_none());
+        assert!(grid.get((3, 0)).is_none());
+        assert_eq!(grid.to_string(), "abc\ndef\n");
+
+        let contents: String = grid.into_iter().map(|cell| cell.contents).collect();
+        assert_eq!(contents, "abcdef");
+    }
+
+    #[test]
+    fn test_ragged() {
+        let result = Grid::try_from(vec![vec![1, 2], vec![3]]);
+
+        assert_eq!(
+            result.unwrap_err(),
+            "ragged grid: row 1 has 1 cells but row 0 has 2"
+        );
+
+        let empty: Grid<u8> = Grid::try_from(vec![]).unwrap();
+        assert_eq!((empty.width(), empty.height()), (0, 0));
+        assert_eq!(empty.into_iter().count(), 0);
+    }
+
+    #[test]
+    fn test_from_lines() {
+        let lines = vec!["abc".to_owned(), "def".to_owned()];
+
+        assert_eq!(Grid::from_lines(&lines).unwrap(), wide());
+        assert_eq!(
+            Grid::from_lines(&["ab", "c"]).unwrap_err(),
+            "ragged grid: row 1 has 1 cells but row 0 has 2"
+        );
+        assert!("ab\nc".parse::<Grid<char>>().is_err());
+
+        assert_eq!(
+            "ab\ncd".parse::<Grid<char>>().unwrap().parse_cells(),
+            tiles()
+        );
+    }
+
+    #[test]
+    fn test_new() {
+        let grid = tiles();
+
+        assert_eq!(grid[(1, 0)], Tile { c: 'b', x: 1, y: 0 });
+        assert_eq!(grid[(0, 1)], Tile { c: 'c', x: 0, y: 1 });
+    }
+
+    #[test]
+    fn test_iterators() {
+        let mut grid = wide();
+
+        let cells: Vec<(Vector2di, char)> = grid.iter().map(|(p, &c)| (p, c)).collect();
+        assert_eq!(cells[1], (Vector2di::new(1, 0), 'b'));
+        assert_eq!(cells[3], (Vector2di::new(0, 1), 'd'));
+
+        let positions: Vec<Vector2di> = grid.positions().collect();
+        assert_eq!(positions.len(), 6);
+        assert_eq!(positions[5], Vector2di::new(2, 1));
+
+        for (position, c) in grid.iter_mut() {
+            if position.y == 1 {
+                *c = c.to_ascii_uppercase();
+            }
+        }
+
+        assert_eq!(grid.to_string(), "abc\nDEF\n");
+    }
+
+    #[test]
+    fn test_iteration_orders() {
+        let grid = wide();
+
+        let row_major: String = grid.iter().map(|(_, c)| c).collect();
+        assert_eq!(row_major, "abcdef");
+
+        let column_major: Vec<(Vector2di, char)> =
+            grid.iter_column_major().map(|(p, &c)| (p, c)).collect();
+        let contents: String = column_major.iter().map(|(_, c)| c).collect();
+        assert_eq!(contents, "adbecf");
+        assert_eq!(column_major[1].0, Vector2di::new(0, 1));
+        assert!(column_major.iter().all(|&(p, c)| grid[p] == c));
+
+        let rows: Vec<&[char]> = grid.rows().collect();
+        assert_eq!(rows, vec![&['a', 'b', 'c'][..], &['d', 'e', 'f'][..]]);
+
+        let columns: Vec<String> = grid.columns().map(|column| column.collect()).collect();
+        assert_eq!(columns, vec!["ad", "be", "cf"]);
+
+        // The same checks on a grid that is taller than it is wide
+        let tall = Grid::try_from(vec![vec![1, 2], vec![3, 4], vec![5, 6]]).unwrap();
+
+        let row_major: Vec<i32> = tall.iter().map(|(_, &n)| n).collect();
+        assert_eq!(row_major, vec![1, 2, 3, 4, 5, 6]);
+
+        let column_major: Vec<i32> = tall.iter_column_major().map(|(_, &n)| n).collect();
+        assert_eq!(column_major, vec![1, 3, 5, 2, 4, 6]);
+
+        assert_eq!(tall.rows().count(), 3);
+
+        let columns: Vec<Vec<i32>> = tall.columns().map(|c| c.copied().collect()).collect();
+        assert_eq!(columns, vec![vec![1, 3, 5], vec![2, 4, 6]]);
+
+        let cells: Vec<(usize, usize)> = tall.into_iter().map(|cell| (cell.x, cell.y)).collect();
+        assert_eq!(cells, vec![(0, 0), (1, 0), (0, 1), (1, 1), (0, 2), (1, 2)]);
+    }
+
+    #[test]
+    fn test_replace_and_swap() {
+        let mut grid = wide();
+
+        assert_eq!(grid.replace((0, 0), 'z'), Some('a'));
+        assert_eq!(grid.replace((3, 0), 'z'), None);
+
+        grid.swap((0, 0), (2, 1));
+        grid.swap((0, 0), (0, 2));
+
+        assert_eq!(grid.to_string(), "fbc\ndez\n");
+    }
+
+    #[test]
+    fn test_regions() {
+        let grid = Grid::try_from(vec![vec![1, 1, 2], vec![2, 1, 2]]).unwrap();
+
+        let mut regions = grid.regions();
+        regions
+            .iter_mut()
+            .for_each(|r| r.sort_by_key(|p| (p.y, p.x)));
+
+        assert_eq!(regions.len(), 3);
+        assert_eq!(
+            regions[0],
+            vec![(0, 0).into(), (1, 0).into(), (1, 1).into()] as Vec<Vector2di>
+        );
+        assert_eq!(
+            regions[1],
+            vec![(2, 0).into(), (2, 1).into()] as Vec<Vector2di>
+        );
+        assert_eq!(regions[2], vec![(0, 1).into()] as Vec<Vector2di>);
+    }
+
+    #[test]
+    fn test_safe_index() {
+        let grid = tiles();
+
+        assert!(matches!(grid.safe_index((1, 1)), CellType::Cell(tile) if tile.c == 'd'));
+        assert_eq!(grid.safe_index((2, 0)), CellType::OutOfBounds);
+        assert_eq!(grid.safe_index((0, -1)), CellType::OutOfBounds);
+    }
+
+    #[test]
+    fn test_relative_lookup() {
+        let grid = tiles();
+        let origin = Vector2di::new(0, 0);
+
+        let right = grid.ortho_neighbour(origin, OrthoDirection::Right);
+        assert_eq!(right.map(|tile| tile.c), Some('b'));
+        assert!(grid.ortho_neighbour(origin, OrthoDirection::Up).is_none());
+
+        let diagonal = grid.cell_from(origin, (1, 1));
+        assert_eq!(diagonal.map(|tile| tile.c), Some('d'));
+        assert!(grid.cell_from(origin, (-1, 1)).is_none());
+    }
+
+    #[test]
+    fn test_direction8() {
+        assert_eq!(Direction8::N.right_45(), Direction8::NE);
+        assert_eq!(Direction8::N.left_45(), Direction8::NW);
+        assert_eq!(Direction8::SW.right(), Direction8::NW);
+        assert_eq!(Direction8::NE.left(), Direction8::NW);
+        assert_eq!(Direction8::E.rotate(4), Direction8::W);
+        assert_eq!(Direction8::E.rotate(-10), Direction8::N);
+        assert_eq!(Direction8::from(OrthoDirection::Left), Direction8::W);
+
+        for direction in Direction8::ALL {
+            let v = Vector2di::from(direction);
+
+            assert_eq!(direction.is_diagonal(), v.x != 0 && v.y != 0);
+            assert_eq!(
+                Vector2di::from(direction.rotate(4)),
+                Vector2di::new(-v.x, -v.y)
+            );
+        }
+    }
+
+    #[test]
+    fn test_vector_arithmetic() {
+        let mut v = Vector2di::new(3, -4);
+
+        assert_eq!(-v, Vector2di::new(-3, 4));
+
+        v += Vector2di::new(1, 1);
+        assert_eq!(v, Vector2di::new(4, -3));
+
+        v -= Vector2di::new(4, 0);
+        assert_eq!(v, Vector2di::new(0, -3));
+    }
+
+    #[test]
+    fn test_vector_geometry() {
+        let a = Vector2di::new(1, 2);
+        let b = Vector2di::new(4, -2);
+
+        assert_eq!(a.manhattan_distance(b), 7);
+        assert_eq!(b.manhattan_distance(a), 7);
+        assert_eq!(a.chebyshev_distance(b), 4);
+        assert_eq!(a.manhattan_distance(a), 0);
+
+        assert_eq!(a.dot(b), 0);
+        assert_eq!(a.cross(b), -10);
+        assert_eq!(b.cross(a), 10);
+        assert_eq!(a.cross(a * 3_i64), 0);
+
+        // Right is clockwise from up, and y points down the grid
+        let up = Vector2di::from(OrthoDirection::Up);
+        let right = Vector2di::from(OrthoDirection::Right);
+        assert!(up.cross(right) > 0);
+
+        for direction in [
+            OrthoDirection::Up,
+            OrthoDirection::Right,
+            OrthoDirection::Down,
+            OrthoDirection::Left,
+        ] {
+            let v = Vector2di::from(direction);
+
+            assert_eq!(v.rotate_right(), Vector2di::from(direction.right()));
+            assert_eq!(v.rotate_left(), Vector2di::from(direction.left()));
+        }
+
+        assert_eq!(a.rotate_right().rotate_right(), -a);
+        assert_eq!(a.rotate_left().rotate_right(), a);
+    }
+
+    #[test]
+    fn test_vector_reduced() {
+        assert_eq!(Vector2di::new(4, -6).reduced(), Vector2di::new(2, -3));
+        assert_eq!(Vector2di::new(0, 5).reduced(), Vector2di::new(0, 1));
+        assert_eq!(Vector2di::new(-7, 0).reduced(), Vector2di::new(-1, 0));
+        assert_eq!(Vector2di::new(3, 5).reduced(), Vector2di::new(3, 5));
+        assert_eq!(Vector2di::default().reduced(), Vector2di::default());
+    }
+
+    #[test]
+    fn test_vector_wrapping_and_conversion() {
+        let bounds = Vector2di::new(11, 7);
+
+        assert_eq!(
+            Vector2di::new(12, -1).rem_euclid(bounds),
+            Vector2di::new(1, 6)
+        );
+        assert_eq!(
+            Vector2di::new(-22, 14).rem_euclid(bounds),
+            Vector2di::default()
+        );
+
+        assert_eq!(<(usize, usize)>::try_from(Vector2di::new(3, 4)), Ok((3, 4)));
+        assert!(<(usize, usize)>::try_from(Vector2di::new(-1, 4)).is_err());
+        assert!(<(usize, usize)>::try_from(Vector2di::new(1, -4)).is_err());
+    }
+
+    #[test]
+    fn test_vector_types() {
+        let small: Vector2<i32> = Vector2::new(3, -4);
+        let wide: Vector2di = small.cast();
+        assert_eq!(wide, Vector2di::new(3, -4));
+        assert_eq!(Vector2di::from((3_i32, -4_i32)), wide);
+
+        let size: Vector2<usize> = Vector2::new(5_usize, 2) * 3;
+        assert_eq!(size, Vector2::new(15, 6));
+        assert_eq!(size.try_cast::<i64>(), Ok(Vector2di::new(15, 6)));
+        assert_eq!(<(usize, usize)>::from(size), (15, 6));
+
+        assert_eq!(
+            wide.try_cast::<u8>().unwrap_err(),
+            "(3, -4) is out of range for u8"
+        );
+        assert!(Vector2di::new(1 << 40, 0).try_cast::<i32>().is_err());
+
+        let half = Vector2::new(1.5, -0.5) + Vector2::new(0.5, 0.5);
+        assert_eq!(half, Vector2::new(2.0, 0.0));
+        assert_eq!(half.dot(Vector2::new(0.25, 4.0)), 0.5);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "overflow")]
+    fn test_vector_overflow() {
+        let _ = Vector2::new(i32::MAX, 0) + Vector2::new(1, 0);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = wide();
+
+        let neighbours: String = grid
+            .neighbours((1, 0))
+            .into_iter()
+            .map(|(_, c)| c)
+            .collect();
+        assert_eq!(neighbours, "cfeda");
+
+        let corner: Vec<Vector2di> = grid
+            .neighbours((2, 1))
+            .into_iter()
+            .map(|(p, _)| p)
+            .collect();
+        assert_eq!(
+            corner,
+            vec![(2, 0).into(), (1, 1).into(), (1, 0).into()] as Vec<Vector2di>
+        );
+
+        assert_eq!(grid.neighbour((0, 1), Direction8::NE), Some(&'b'));
+        assert_eq!(grid.neighbour((0, 1), Direction8::SE), None);
+    }
+
+    #[test]
+    fn test_ray() {
+        let grid = wide();
+
+        let ray: String = grid.ray((0, 0), Direction8::E).map(|(_, c)| c).collect();
+        assert_eq!(ray, "abc");
+
+        let ray: String = grid.ray((2, 1), Direction8::NW).map(|(_, c)| c).collect();
+        assert_eq!(ray, "fb");
+
+        let ray: Vec<Vector2di> = grid.ray((0, 1), (2, -1)).map(|(p, _)| p).collect();
+        assert_eq!(ray, vec![(0, 1).into(), (2, 0).into()] as Vec<Vector2di>);
+
+        assert_eq!(grid.ray((3, 0), Direction8::W).count(), 0);
+        assert_eq!(grid.ray((1, 1), (0, 0)).count(), 1);
+    }
+
+    #[test]
+    fn test_ray_until_and_read() {
+        let grid = Grid::from_lines(&["..#.", "abcd"]).unwrap();
+
+        let open: Vec<Vector2di> = grid
+            .ray((0, 0), Direction8::E)
+            .until(|&c| c == '#')
+            .map(|(p, _)| p)
+            .collect();
+        assert_eq!(open, vec![(0, 0).into(), (1, 0).into()] as Vec<Vector2di>);
+
+        assert_eq!(
+            grid.ray((2, 0), Direction8::E).until(|&c| c == '#').count(),
+            0
+        );
+
+        assert_eq!(grid.read::<String>((0, 1), Direction8::E, 3), "abc");
+        assert_eq!(grid.read::<String>((2, 1), Direction8::E, 3), "cd");
+        assert_eq!(
+            grid.read::<Vec<char>>((3, 1), Direction8::NW, 2),
+            vec!['d', '#']
+        );
+    }
+
+    #[test]
+    fn test_wrapping() {
+        let grid = wide().with_topology(Topology::Wrapping);
+
+        assert_eq!(grid.wrap((3, -1)), Some(Vector2di::new(0, 1)));
+        assert_eq!(grid.get((-1, 0)), Some(&'c'));
+        assert_eq!(grid[(5, 3)], 'f');
+        assert!(grid.contains((100, -100)));
+        assert_eq!(wide().wrap((3, 0)), None);
+        assert!(!wide().contains((3, 0)));
+
+        let neighbours: String = grid.neighbours((0, 0)).iter().map(|(_, &c)| c).collect();
+        assert_eq!(neighbours, "debedfcf");
+        assert!(grid
+            .safe_ortho_neighbours((2, 1))
+            .iter()
+            .all(|&(position, _)| wide().contains(position)));
+
+        // The walk goes round and stops before reaching the start again
+        let ray: String = grid.ray((1, 0), (1, 1)).map(|(_, &c)| c).collect();
+        assert_eq!(ray, "bfaecd");
+        assert_eq!(grid.ray((1, 0), (0, 0)).count(), 1);
+        assert_eq!(
+            grid.ray((-2, 0), (-1, 0)).next().unwrap().0,
+            Vector2di::new(1, 0)
+        );
+
+        let mut counts = Grid::filled(2, 2, 0).with_topology(Topology::Wrapping);
+        counts[(3, 3)] += 1;
+        assert_eq!(counts[(1, 1)], 1);
+        assert!(Grid::<u8>::filled(0, 0, 0)
+            .with_topology(Topology::Wrapping)
+            .wrap((0, 0))
+            .is_none());
+    }
 
     #[test]
     fn test_cell_ord_and_eq() {
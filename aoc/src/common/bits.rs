@@ -0,0 +1,275 @@
+use std::fmt::{Display, Write};
+
+use crate::common::{Grid, Vector2di};
+
+const BITS: usize = u64::BITS as usize;
+
+/**
+A grid of flags packed into 64 bits per word, for keeping track of which cells have been visited
+or are occupied without a `Vec` of positions to search through.
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    /// A grid with every flag cleared
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            words: vec![0; (width * height).div_ceil(BITS)],
+        }
+    }
+
+    /// A grid the same size as `grid` with the flag set wherever `set` holds for the contents
+    pub fn from_grid<T>(grid: &Grid<T>, set: impl Fn(&T) -> bool) -> Self {
+        let mut bits = Self::new(grid.width(), grid.height());
+
+        for (position, _) in grid.iter().filter(|(_, contents)| set(contents)) {
+            bits.set(position);
+        }
+
+        bits
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, loc: impl Into<Vector2di>) -> bool {
+        self.index_of(loc).is_some()
+    }
+
+    fn index_of(&self, loc: impl Into<Vector2di>) -> Option<usize> {
+        let (x, y): (usize, usize) = loc.into().try_into().ok()?;
+
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+
+    fn expect_index(&self, loc: impl Into<Vector2di>) -> usize {
+        self.index_of(loc).expect("position is outside the grid")
+    }
+
+    /// Whether the flag is set at the given position. Positions outside the grid are never set.
+    pub fn test(&self, loc: impl Into<Vector2di>) -> bool {
+        self.index_of(loc)
+            .is_some_and(|i| self.words[i / BITS] & (1 << (i % BITS)) != 0)
+    }
+
+    /// Set the flag at the given position, returning true if it wasn't already set
+    pub fn set(&mut self, loc: impl Into<Vector2di>) -> bool {
+        let i = self.expect_index(loc);
+        let word = &mut self.words[i / BITS];
+        let was_clear = *word & (1 << (i % BITS)) == 0;
+
+        *word |= 1 << (i % BITS);
+
+        was_clear
+    }
+
+    /// Clear the flag at the given position, returning true if it was set
+    pub fn clear(&mut self, loc: impl Into<Vector2di>) -> bool {
+        let i = self.expect_index(loc);
+        let word = &mut self.words[i / BITS];
+        let was_set = *word & (1 << (i % BITS)) != 0;
+
+        *word &= !(1 << (i % BITS));
+
+        was_set
+    }
+
+    /// The number of flags that are set
+    pub fn count(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+
+    fn combine(&self, other: &BitGrid, op: impl Fn(u64, u64) -> u64) -> Self {
+        assert!(
+            self.width == other.width && self.height == other.height,
+            "bit grids are different sizes"
+        );
+
+        Self {
+            width: self.width,
+            height: self.height,
+            words: self
+                .words
+                .iter()
+                .zip(&other.words)
+                .map(|(&a, &b)| op(a, b))
+                .collect(),
+        }
+    }
+
+    /// The flags set in either grid. Panics if the grids are different sizes.
+    pub fn union(&self, other: &BitGrid) -> Self {
+        self.combine(other, |a, b| a | b)
+    }
+
+    /// The flags set in both grids. Panics if the grids are different sizes.
+    pub fn intersection(&self, other: &BitGrid) -> Self {
+        self.combine(other, |a, b| a & b)
+    }
+
+    /// The positions where the flag is set, in row-major order
+    pub fn iter(&self) -> impl Iterator<Item = Vector2di> + '_ {
+        self.words.iter().enumerate().flat_map(move |(w, &word)| {
+            // Pick off the lowest set bit of the word until there are none left
+            std::iter::successors((word != 0).then_some(word), |&rest| {
+                let rest = rest & (rest - 1);
+
+                (rest != 0).then_some(rest)
+            })
+            .map(move |rest| {
+                let i = w * BITS + rest.trailing_zeros() as usize;
+
+                Vector2di::from((i % self.width, i / self.width))
+            })
+        })
+    }
+
+    /**
+    Draw `grid` with `glyph` in place of the contents of every cell where the flag is set. Panics
+    if the grids are different sizes.
+    */
+    pub fn overlay<'a, T>(&'a self, grid: &'a Grid<T>, glyph: char) -> Overlay<'a, T> {
+        assert!(
+            self.width == grid.width() && self.height == grid.height(),
+            "bit grid and grid are different sizes"
+        );
+
+        Overlay {
+            bits: self,
+            grid,
+            glyph,
+        }
+    }
+}
+
+/// A grid drawn with a bit grid on top of it, see [`BitGrid::overlay`]
+pub struct Overlay<'a, T> {
+    bits: &'a BitGrid,
+    grid: &'a Grid<T>,
+    glyph: char,
+}
+
+impl<T: Copy + Into<char>> Display for Overlay<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.grid.rows().enumerate() {
+            for (x, contents) in row.iter().enumerate() {
+                if self.bits.test((x, y)) {
+                    f.write_char(self.glyph)?;
+                } else {
+                    f.write_char((*contents).into())?;
+                }
+            }
+
+            f.write_str("\n")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for BitGrid {
+    /// Draw set flags as `#` and clear ones as `.`
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                f.write_char(if self.test((x, y)) { '#' } else { '.' })?;
+            }
+
+            f.write_str("\n")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::BitGrid;
+    use crate::common::{Grid, Vector2di};
+
+    #[test]
+    fn test_set_and_clear() {
+        // Wide enough that the flags span more than one word
+        let mut bits = BitGrid::new(10, 10);
+        assert!(bits.is_empty());
+
+        assert!(bits.set((3, 0)));
+        assert!(!bits.set((3, 0)));
+        assert!(bits.set((9, 9)));
+        assert!(bits.set((4, 6)));
+
+        assert!(bits.test((3, 0)));
+        assert!(bits.test(Vector2di::new(9, 9)));
+        assert!(!bits.test((0, 3)));
+        assert!(!bits.test((-1, 0)));
+        assert!(!bits.test((10, 0)));
+        assert_eq!(bits.count(), 3);
+
+        assert!(bits.clear((4, 6)));
+        assert!(!bits.clear((4, 6)));
+        assert_eq!(bits.count(), 2);
+
+        assert_eq!(
+            bits.iter().collect::<Vec<Vector2di>>(),
+            vec![Vector2di::new(3, 0), Vector2di::new(9, 9)]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "position is outside the grid")]
+    fn test_set_outside() {
+        BitGrid::new(2, 2).set((2, 0));
+    }
+
+    #[test]
+    fn test_union_and_intersection() {
+        let mut a = BitGrid::new(3, 2);
+        let mut b = BitGrid::new(3, 2);
+
+        a.set((0, 0));
+        a.set((1, 1));
+        b.set((1, 1));
+        b.set((2, 0));
+
+        assert_eq!(a.union(&b).to_string(), "#.#\n.#.\n");
+        assert_eq!(a.intersection(&b).to_string(), "...\n.#.\n");
+        assert_eq!(a.intersection(&b).count(), 1);
+    }
+
+    #[test]
+    fn test_overlay() {
+        let grid: Grid<char> = ".#.\n...".parse().unwrap();
+
+        let walls = BitGrid::from_grid(&grid, |&c| c == '#');
+        assert_eq!(
+            walls.iter().collect::<Vec<Vector2di>>(),
+            vec![Vector2di::new(1, 0)]
+        );
+
+        let mut path = BitGrid::new(3, 2);
+        path.set((0, 0));
+        path.set((0, 1));
+        path.set((1, 1));
+
+        assert_eq!(path.overlay(&grid, 'O').to_string(), "O#.\nOO.\n");
+    }
+}
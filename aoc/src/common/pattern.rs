@@ -0,0 +1,210 @@
+use crate::common::{Grid, Vector2di};
+
+/**
+A small rectangle of characters to search for in a grid. Templates are written with rows separated
+by `/` or new lines, and `.` matches any character, so `M.S/.A./M.S` is an X of `MAS`s. Use
+[`Template::with_wildcard`] when `.` needs to match itself.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct Template {
+    cells: Grid<Option<char>>,
+}
+
+impl Template {
+    pub const WILDCARD: char = '.';
+
+    /**
+    Parse a template where `wildcard` matches any character. New lines at the start and end are
+    ignored, but every row in between must have at least one character.
+    */
+    pub fn with_wildcard(pattern: &str, wildcard: char) -> Result<Self, String> {
+        let pattern = pattern.trim_matches('\n');
+
+        if pattern.is_empty() {
+            return Err("empty template".to_owned());
+        }
+
+        let rows: Vec<Vec<Option<char>>> = pattern
+            .split(['/', '\n'])
+            .enumerate()
+            .map(|(i, row)| {
+                if row.is_empty() {
+                    return Err(format!("invalid template: row {} is empty", i));
+                }
+
+                Ok(row
+                    .chars()
+                    .map(|c| if c == wildcard { None } else { Some(c) })
+                    .collect())
+            })
+            .collect::<Result<_, String>>()?;
+
+        let cells = Grid::try_from(rows).map_err(|e| format!("invalid template: {}", e))?;
+
+        Ok(Self { cells })
+    }
+
+    pub fn width(&self) -> usize {
+        self.cells.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.height()
+    }
+
+    /**
+    Every distinct rotation and reflection of the template, starting with the template itself.
+    Symmetrical templates have fewer than eight.
+    */
+    pub fn orientations(&self) -> Vec<Template> {
+        let mut orientations: Vec<Template> = vec![];
+
+        for cells in [self.cells.clone(), self.cells.flip_horizontal()] {
+            for turns in 0..4 {
+                let orientation = Template {
+                    cells: cells.rotate(turns),
+                };
+
+                if !orientations.contains(&orientation) {
+                    orientations.push(orientation);
+                }
+            }
+        }
+
+        orientations
+    }
+
+    /// Whether the template matches the grid with its top-left corner at `origin`
+    pub fn matches_at(&self, grid: &Grid<char>, origin: impl Into<Vector2di>) -> bool {
+        let origin = origin.into();
+
+        self.cells
+            .iter()
+            .all(|(position, expected)| match expected {
+                Some(expected) => grid.get(origin + position) == Some(expected),
+                None => grid.contains(origin + position),
+            })
+    }
+}
+
+impl TryFrom<&str> for Template {
+    type Error = String;
+
+    /// Parse a template with `.` as the wildcard, see [`Template::with_wildcard`]
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Self::with_wildcard(value, Self::WILDCARD)
+    }
+}
+
+impl Grid<char> {
+    /// The top-left corner of every placement of the template in the grid, in row-major order
+    pub fn find(&self, template: &Template) -> Vec<Vector2di> {
+        // Placements that would hang off the bottom or right of the grid can be skipped
+        let width = (self.width() + 1).saturating_sub(template.width());
+        let height = (self.height() + 1).saturating_sub(template.height());
+
+        (0..height)
+            .flat_map(|y| (0..width).map(move |x| Vector2di::from((x, y))))
+            .filter(|&origin| template.matches_at(self, origin))
+            .collect()
+    }
+
+    /**
+    Find the template in any orientation. Each match is the top-left corner of the placement and
+    the index of the orientation that matched in [`Template::orientations`].
+    */
+    pub fn find_oriented(&self, template: &Template) -> Vec<(Vector2di, usize)> {
+        template
+            .orientations()
+            .iter()
+            .enumerate()
+            .flat_map(|(i, orientation)| {
+                self.find(orientation)
+                    .into_iter()
+                    .map(move |position| (position, i))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Template;
+    use crate::common::{Grid, Vector2di};
+
+    #[test]
+    fn test_parse() {
+        let template = Template::try_from("M.S/.A./M.S").unwrap();
+
+        assert_eq!((template.width(), template.height()), (3, 3));
+        assert_eq!(Template::try_from("M.S\n.A.\nM.S").unwrap(), template);
+
+        assert_eq!(Template::try_from("\nM.S\n.A.\nM.S\n").unwrap(), template);
+
+        assert!(Template::try_from("").is_err());
+        assert!(Template::try_from("ab/c").is_err());
+        assert_eq!(
+            Template::try_from("ab//cd").unwrap_err(),
+            "invalid template: row 1 is empty"
+        );
+        assert!(Template::try_from("ab\n\ncd").is_err());
+    }
+
+    #[test]
+    fn test_with_wildcard() {
+        let grid: Grid<char> = "#.#\n.##".parse().unwrap();
+
+        let template = Template::with_wildcard("#?#/.#?", '?').unwrap();
+        assert_eq!(grid.find(&template), vec![Vector2di::new(0, 0)]);
+
+        // With the default wildcard the dots match anything
+        assert_eq!(grid.find(&Template::try_from("#.").unwrap()).len(), 2);
+        assert_eq!(
+            grid.find(&Template::with_wildcard("#.", '?').unwrap())
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_orientations() {
+        let count = |pattern: &str| Template::try_from(pattern).unwrap().orientations().len();
+
+        assert_eq!(count("XMAS"), 4);
+        assert_eq!(count("X.../.M../..A./...S"), 4);
+        assert_eq!(count("M.S/.A./M.S"), 4);
+        assert_eq!(count("ab/cd"), 8);
+        assert_eq!(count("a"), 1);
+
+        let template = Template::try_from("ab").unwrap();
+        assert_eq!(template.orientations()[0], template);
+    }
+
+    #[test]
+    fn test_find() {
+        let grid: Grid<char> = "abab\nbaba\nabab".parse().unwrap();
+
+        let template = Template::try_from("ab/ba").unwrap();
+        assert_eq!(
+            grid.find(&template),
+            vec![(0, 0).into(), (2, 0).into(), (1, 1).into()] as Vec<Vector2di>
+        );
+
+        let wildcard = Template::try_from("a./.a").unwrap();
+        assert_eq!(grid.find(&wildcard).len(), 3);
+
+        let too_big = Template::try_from("ababa").unwrap();
+        assert!(grid.find(&too_big).is_empty());
+    }
+
+    #[test]
+    fn test_find_oriented() {
+        let grid: Grid<char> = "XMAS\nM..A\nA..M\nSAMX".parse().unwrap();
+
+        let matches = grid.find_oriented(&Template::try_from("XMAS").unwrap());
+
+        assert_eq!(matches.len(), 4);
+        assert!(matches.contains(&((0, 0).into(), 0)));
+        assert!(matches.contains(&((0, 3).into(), 2)));
+    }
+}
@@ -0,0 +1,549 @@
+use crate::common::bits::BitGrid;
+use crate::common::{Grid, OrthoDirection, Vector2di};
+
+/// Which neighbours of a cell count as connected to it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Connectivity {
+    /// Only the orthogonal neighbours
+    #[default]
+    Four,
+    /// The orthogonal and the diagonal neighbours
+    Eight,
+}
+
+/// The smallest rectangle containing a set of positions. Both corners are inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoundingBox {
+    pub min: Vector2di,
+    pub max: Vector2di,
+}
+
+impl BoundingBox {
+    pub(super) fn around(position: Vector2di) -> Self {
+        Self {
+            min: position,
+            max: position,
+        }
+    }
+
+    pub(super) fn extend(&mut self, position: Vector2di) {
+        self.min = Vector2di::new(self.min.x.min(position.x), self.min.y.min(position.y));
+        self.max = Vector2di::new(self.max.x.max(position.x), self.max.y.max(position.y));
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max.x - self.min.x + 1) as usize
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max.y - self.min.y + 1) as usize
+    }
+
+    pub fn contains(&self, position: Vector2di) -> bool {
+        (self.min.x..=self.max.x).contains(&position.x)
+            && (self.min.y..=self.max.y).contains(&position.y)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegionInfo<T> {
+    /// The region's label in [`Labelling::labels`], which is also its index in
+    /// [`Labelling::regions`]
+    pub id: usize,
+    /// The contents of the first cell of the region in row-major order
+    pub contents: T,
+    pub cells: Vec<Vector2di>,
+    pub bounds: BoundingBox,
+}
+
+/**
+A straight section of a region's boundary. The ends are corners of cells, where the top-left
+corner of the cell at `(x, y)` is `(x, y)` and its bottom-right corner is `(x + 1, y + 1)`, and
+`start` is always above or to the left of `end`. `facing` points out of the region.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Edge {
+    pub start: Vector2di,
+    pub end: Vector2di,
+    pub facing: OrthoDirection,
+}
+
+impl Edge {
+    pub fn length(&self) -> usize {
+        self.start.manhattan_distance(self.end)
+    }
+}
+
+impl<T> RegionInfo<T> {
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    /**
+    Which positions are in the region, over the bounding box with an extra border of one cell all
+    the way round. The offset converts a position in the grid to a position in the mask.
+    */
+    fn mask(&self) -> (Grid<bool>, Vector2di) {
+        let offset = Vector2di::new(1 - self.bounds.min.x, 1 - self.bounds.min.y);
+        let (width, height) = (self.bounds.width() + 2, self.bounds.height() + 2);
+
+        let mut mask = Grid::filled(width, height, false);
+
+        for &cell in &self.cells {
+            mask[cell + offset] = true;
+        }
+
+        (mask, offset)
+    }
+
+    /// The number of cell edges between the region and the cells around it
+    pub fn perimeter(&self) -> usize {
+        let (mask, offset) = self.mask();
+
+        self.cells
+            .iter()
+            .map(|&cell| {
+                mask.safe_ortho_neighbours(cell + offset)
+                    .into_iter()
+                    .filter(|(_, &inside)| !inside)
+                    .count()
+            })
+            .sum()
+    }
+
+    /**
+    The number of straight sides of the region, counting the sides around any holes.
+
+    Following the wall round the outside of the region only finds the outer sides, so instead the
+    corners are counted, since every side starts at exactly one corner. Each cell is checked for a
+    corner in each of the four diagonal directions. The two orthogonal neighbours either side of
+    the diagonal both being outside the region makes an outside corner, and both being inside with
+    the diagonal cell outside makes an inside corner.
+    */
+    pub fn sides(&self) -> usize {
+        let (mask, offset) = self.mask();
+
+        let inside = |position: Vector2di| mask.get(position).copied().unwrap_or(false);
+
+        let mut corners = 0;
+
+        for &cell in &self.cells {
+            let cell = cell + offset;
+
+            for direction in [
+                OrthoDirection::Up,
+                OrthoDirection::Right,
+                OrthoDirection::Down,
+                OrthoDirection::Left,
+            ] {
+                let a = cell + direction.into();
+                let b = cell + direction.right().into();
+                let diagonal = a + direction.right().into();
+
+                match (inside(a), inside(b)) {
+                    (false, false) => corners += 1,
+                    (true, true) if !inside(diagonal) => corners += 1,
+                    _ => {}
+                }
+            }
+        }
+
+        corners
+    }
+
+    /**
+    The number of holes in the region, which are orthogonally connected areas of cells that are
+    completely surrounded by the region.
+    */
+    pub fn holes(&self) -> usize {
+        let (mask, _) = self.mask();
+
+        // The border of the mask is all outside the region, so it is one connected area
+        let outside = mask
+            .label_regions(Connectivity::Four)
+            .regions
+            .into_iter()
+            .filter(|region| !region.contents)
+            .count();
+
+        outside - 1
+    }
+
+    /// The straight sections of the region's boundary, see [`Edge`]
+    pub fn edges(&self) -> Vec<Edge> {
+        let (mask, offset) = self.mask();
+
+        let mut cells = self.cells.clone();
+        let mut edges = vec![];
+
+        for facing in [
+            OrthoDirection::Up,
+            OrthoDirection::Right,
+            OrthoDirection::Down,
+            OrthoDirection::Left,
+        ] {
+            let horizontal = matches!(facing, OrthoDirection::Up | OrthoDirection::Down);
+
+            // Order the cells so that cells along the same edge are next to each other
+            if horizontal {
+                cells.sort_by_key(|cell| (cell.y, cell.x));
+            } else {
+                cells.sort_by_key(|cell| (cell.x, cell.y));
+            }
+
+            let mut current: Option<Edge> = None;
+
+            for &cell in &cells {
+                if mask[cell + offset + facing.into()] {
+                    continue;
+                }
+
+                // The corners at either end of the cell's edge in the direction it is facing
+                let (start, end) = match facing {
+                    OrthoDirection::Up => (cell, cell + (1, 0).into()),
+                    OrthoDirection::Right => (cell + (1, 0).into(), cell + (1, 1).into()),
+                    OrthoDirection::Down => (cell + (0, 1).into(), cell + (1, 1).into()),
+                    OrthoDirection::Left => (cell, cell + (0, 1).into()),
+                };
+
+                match &mut current {
+                    Some(edge) if edge.end == start => edge.end = end,
+                    _ => {
+                        edges.extend(current.take());
+                        current = Some(Edge { start, end, facing });
+                    }
+                }
+            }
+
+            edges.extend(current);
+        }
+
+        edges
+    }
+}
+
+/// Every cell of a grid labelled with the region it belongs to
+#[derive(Debug, Clone, PartialEq)]
+pub struct Labelling<T> {
+    pub labels: Grid<usize>,
+    pub regions: Vec<RegionInfo<T>>,
+}
+
+impl<T> Labelling<T> {
+    pub fn label(&self, loc: impl Into<Vector2di>) -> Option<usize> {
+        self.labels.get(loc).copied()
+    }
+
+    pub fn region_at(&self, loc: impl Into<Vector2di>) -> Option<&RegionInfo<T>> {
+        Some(&self.regions[self.label(loc)?])
+    }
+}
+
+impl<T> Grid<T> {
+    fn connected(&self, loc: Vector2di, connectivity: Connectivity) -> Vec<(Vector2di, &T)> {
+        match connectivity {
+            Connectivity::Four => self.safe_ortho_neighbours(loc),
+            Connectivity::Eight => self.neighbours(loc),
+        }
+    }
+
+    /**
+    Flood fill outwards from `start`, marking each cell that is reached in `visited`. A neighbour
+    is added to the region if `same` holds for the contents of the cell it was reached from and its
+    own contents.
+    */
+    pub(super) fn flood(
+        &self,
+        start: Vector2di,
+        connectivity: Connectivity,
+        same: &impl Fn(&T, &T) -> bool,
+        visited: &mut BitGrid,
+    ) -> Vec<Vector2di> {
+        let Some(start) = self.wrap(start) else {
+            return vec![];
+        };
+
+        visited.set(start);
+
+        let mut cells = vec![start];
+        let mut buffer = vec![start];
+
+        while let Some(position) = buffer.pop() {
+            let contents = &self[position];
+
+            for (neighbour, other) in self.connected(position, connectivity) {
+                if !visited.test(neighbour) && same(contents, other) {
+                    visited.set(neighbour);
+                    cells.push(neighbour);
+                    buffer.push(neighbour);
+                }
+            }
+        }
+
+        cells
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /**
+    Split the grid into connected regions, where neighbouring cells are in the same region if
+    `same` holds for their contents. Every cell is visited a fixed number of times, so this runs in
+    time linear in the size of the grid.
+
+    Regions are numbered in row-major order of their first cell. If `same` isn't transitive, the
+    cells of a region are only guaranteed to be similar to the cells next to them.
+
+    On a wrapping grid, regions join up across opposite edges. Their bounds and shape are still
+    measured as the cells are laid out in the grid, so a region that wraps round has sides where it
+    meets the edges.
+    */
+    pub fn label_regions_by(
+        &self,
+        connectivity: Connectivity,
+        same: impl Fn(&T, &T) -> bool,
+    ) -> Labelling<T> {
+        let mut visited = BitGrid::new(self.width, self.height);
+        let mut labels = vec![0; self.cells.len()];
+        let mut regions = vec![];
+
+        for index in 0..self.cells.len() {
+            let start = self.position_of(index);
+
+            if visited.test(start) {
+                continue;
+            }
+
+            let cells = self.flood(start, connectivity, &same, &mut visited);
+
+            let mut bounds = BoundingBox::around(start);
+
+            for &cell in &cells {
+                labels[self.index_of(cell).unwrap()] = regions.len();
+                bounds.extend(cell);
+            }
+
+            regions.push(RegionInfo {
+                id: regions.len(),
+                contents: self.cells[index].clone(),
+                cells,
+                bounds,
+            });
+        }
+
+        Labelling {
+            labels: Grid {
+                width: self.width,
+                height: self.height,
+                cells: labels,
+                topology: self.topology,
+            },
+            regions,
+        }
+    }
+}
+
+impl<T: Clone + PartialEq> Grid<T> {
+    /// Split the grid into connected regions of cells with equal contents
+    pub fn label_regions(&self, connectivity: Connectivity) -> Labelling<T> {
+        self.label_regions_by(connectivity, |a, b| a == b)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{BoundingBox, Connectivity};
+    use crate::common::{Grid, OrthoDirection, Topology, Vector2di};
+
+    #[test]
+    fn test_label_regions() {
+        let grid: Grid<char> = "aab\nbab\nbab\nccb".parse().unwrap();
+
+        let labelling = grid.label_regions(Connectivity::Four);
+
+        assert_eq!(labelling.regions.len(), 4);
+        assert_eq!(
+            labelling.labels.rows().collect::<Vec<&[usize]>>(),
+            vec![&[0, 0, 1][..], &[2, 0, 1], &[2, 0, 1], &[3, 3, 1]]
+        );
+
+        let b = labelling.region_at((2, 0)).unwrap();
+        assert_eq!((b.id, b.contents, b.area()), (1, 'b', 4));
+        assert_eq!(
+            b.bounds,
+            BoundingBox {
+                min: Vector2di::new(2, 0),
+                max: Vector2di::new(2, 3)
+            }
+        );
+
+        let other_b = labelling.region_at((0, 2)).unwrap();
+        assert_eq!((other_b.id, other_b.area()), (2, 2));
+        assert_eq!((other_b.bounds.width(), other_b.bounds.height()), (1, 2));
+        assert!(other_b.bounds.contains(Vector2di::new(0, 1)));
+        assert!(!other_b.bounds.contains(Vector2di::new(1, 1)));
+
+        assert!(labelling.region_at((3, 0)).is_none());
+    }
+
+    #[test]
+    fn test_label_regions_wrapping() {
+        let grid = "a.a\n...\na.a"
+            .parse::<Grid<char>>()
+            .unwrap()
+            .with_topology(Topology::Wrapping);
+
+        // The four corners are all next to each other across the edges
+        let labelling = grid.label_regions(Connectivity::Four);
+        assert_eq!(labelling.regions.len(), 2);
+        assert_eq!(labelling.region_at((2, 2)).unwrap().area(), 4);
+        assert_eq!(labelling.labels.topology(), Topology::Wrapping);
+        assert_eq!(labelling.label((-1, -1)), labelling.label((2, 2)));
+
+        let bounded = grid.with_topology(Topology::Bounded);
+        assert_eq!(bounded.label_regions(Connectivity::Four).regions.len(), 5);
+    }
+
+    // The counter-examples from day 12: an island that defeats following the left-hand wall, and a
+    // hole that defeats detecting unexplored walls
+    const EXAMPLE2: &str = "\
+AAAAAA
+AAABBA
+AAABBA
+ABBAAA
+ABBAAA
+AAAAAA";
+
+    const EXAMPLE3: &str = "\
+AAAA
+ABBA
+ABBA
+AAAA";
+
+    #[test]
+    fn test_geometry() {
+        let labelling = EXAMPLE2
+            .parse::<Grid<char>>()
+            .unwrap()
+            .label_regions(Connectivity::Four);
+        assert_eq!(labelling.regions.len(), 3);
+
+        let a = labelling.region_at((0, 0)).unwrap();
+        assert_eq!(a.area(), 28);
+        assert_eq!(a.perimeter(), 24 + 8 + 8);
+        assert_eq!(a.sides(), 12);
+        assert_eq!(a.holes(), 2);
+        assert_eq!(a.edges().len(), a.sides());
+
+        for b in [(3, 1), (1, 3)] {
+            let b = labelling.region_at(b).unwrap();
+
+            assert_eq!(
+                (b.area(), b.perimeter(), b.sides(), b.holes()),
+                (4, 8, 4, 0)
+            );
+        }
+
+        let labelling = EXAMPLE3
+            .parse::<Grid<char>>()
+            .unwrap()
+            .label_regions(Connectivity::Four);
+
+        let a = labelling.region_at((0, 0)).unwrap();
+        assert_eq!(
+            (a.area(), a.perimeter(), a.sides(), a.holes()),
+            (12, 24, 8, 1)
+        );
+
+        let b = labelling.region_at((1, 1)).unwrap();
+        assert_eq!(
+            (b.area(), b.perimeter(), b.sides(), b.holes()),
+            (4, 8, 4, 0)
+        );
+        assert_eq!(
+            b.bounds,
+            BoundingBox {
+                min: Vector2di::new(1, 1),
+                max: Vector2di::new(2, 2)
+            }
+        );
+    }
+
+    #[test]
+    fn test_edges() {
+        let labelling = "aab\nabb"
+            .parse::<Grid<char>>()
+            .unwrap()
+            .label_regions(Connectivity::Four);
+
+        let a = labelling.region_at((0, 0)).unwrap();
+        let edges: Vec<(i64, i64, i64, i64, OrthoDirection)> = a
+            .edges()
+            .iter()
+            .map(|e| (e.start.x, e.start.y, e.end.x, e.end.y, e.facing))
+            .collect();
+
+        assert_eq!(
+            edges,
+            vec![
+                (0, 0, 2, 0, OrthoDirection::Up),
+                (1, 1, 1, 2, OrthoDirection::Right),
+                (2, 0, 2, 1, OrthoDirection::Right),
+                (1, 1, 2, 1, OrthoDirection::Down),
+                (0, 2, 1, 2, OrthoDirection::Down),
+                (0, 0, 0, 2, OrthoDirection::Left),
+            ]
+        );
+        assert_eq!(
+            a.edges().iter().map(|e| e.length()).sum::<usize>(),
+            a.perimeter()
+        );
+        assert_eq!(a.sides(), 6);
+    }
+
+    #[test]
+    fn test_eight_connectivity() {
+        let grid: Grid<char> = "x.x\n.x.\nx.x".parse().unwrap();
+
+        let four = grid.label_regions(Connectivity::Four);
+        assert_eq!(four.regions.len(), 9);
+
+        let eight = grid.label_regions(Connectivity::Eight);
+        assert_eq!(eight.regions.len(), 2);
+        assert_eq!(eight.regions[0].area(), 5);
+        assert_eq!(eight.regions[1].area(), 4);
+        assert_eq!(eight.label((2, 2)), Some(0));
+    }
+
+    #[test]
+    fn test_custom_predicate() {
+        let heights = Grid::try_from(vec![vec![1, 2, 3, 9], vec![5, 4, 8, 9]]).unwrap();
+
+        // Cells are connected if the height changes by at most 1
+        let labelling =
+            heights.label_regions_by(Connectivity::Four, |a: &i32, b: &i32| (a - b).abs() <= 1);
+
+        assert_eq!(labelling.regions.len(), 3);
+        assert_eq!(labelling.region_at((0, 1)).unwrap().area(), 2);
+        assert_eq!(labelling.label((1, 0)), labelling.label((2, 0)));
+        assert_eq!(labelling.region_at((2, 1)).unwrap().cells.len(), 3);
+        assert_eq!(labelling.region_at((3, 0)).unwrap().contents, 9);
+    }
+
+    #[test]
+    fn test_large_grid() {
+        // A checkerboard has no orthogonal neighbours in common, so every cell is its own region
+        let size = 300;
+        let board = Grid::try_from(
+            (0..size)
+                .map(|y| (0..size).map(|x| (x + y) % 2).collect())
+                .collect::<Vec<Vec<usize>>>(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            board.label_regions(Connectivity::Four).regions.len(),
+            size * size
+        );
+        assert_eq!(board.label_regions(Connectivity::Eight).regions.len(), 2);
+    }
+}
@@ -0,0 +1,381 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+use crate::common::{Grid, Vector2di};
+
+/**
+Everything found by a search: the shortest distance to each node that was reached, and every
+predecessor of each node that lies on a shortest path to it.
+*/
+#[derive(Debug, Clone)]
+pub struct SearchResult<N> {
+    start: N,
+    distances: HashMap<N, usize>,
+    predecessors: HashMap<N, Vec<N>>,
+}
+
+impl<N: Eq + Hash + Clone> SearchResult<N> {
+    fn new(start: N) -> Self {
+        Self {
+            distances: HashMap::from([(start.clone(), 0)]),
+            predecessors: HashMap::new(),
+            start,
+        }
+    }
+
+    /**
+    Record that `node` can be reached in `distance` by way of `from`. Returns true if this is a new
+    shortest distance, in which case the node needs to be explored again.
+    */
+    fn relax(&mut self, from: &N, node: N, distance: usize) -> bool {
+        match self.distances.get(&node) {
+            Some(&known) if known < distance => false,
+            Some(&known) if known == distance => {
+                let predecessors = self.predecessors.entry(node).or_default();
+
+                if !predecessors.contains(from) {
+                    predecessors.push(from.clone());
+                }
+
+                false
+            }
+            _ => {
+                self.distances.insert(node.clone(), distance);
+                self.predecessors.insert(node, vec![from.clone()]);
+
+                true
+            }
+        }
+    }
+
+    pub fn start(&self) -> &N {
+        &self.start
+    }
+
+    /// The length of the shortest path to `node`, or `None` if it wasn't reached
+    pub fn distance(&self, node: &N) -> Option<usize> {
+        self.distances.get(node).copied()
+    }
+
+    /// Every node that was reached, with the length of the shortest path to it
+    pub fn distances(&self) -> &HashMap<N, usize> {
+        &self.distances
+    }
+
+    /// The nodes immediately before `node` on each of the shortest paths to it
+    pub fn predecessors(&self, node: &N) -> &[N] {
+        self.predecessors.get(node).map_or(&[], Vec::as_slice)
+    }
+
+    /// One of the shortest paths from the start to `goal`, including both ends
+    pub fn path(&self, goal: &N) -> Option<Vec<N>> {
+        self.distances.get(goal)?;
+
+        let mut path = vec![goal.clone()];
+
+        while let Some(previous) = self.predecessors(path.last().unwrap()).first() {
+            path.push(previous.clone());
+        }
+
+        path.reverse();
+
+        Some(path)
+    }
+
+    /// Every one of the shortest paths from the start to `goal`, including both ends
+    pub fn all_paths(&self, goal: &N) -> Vec<Vec<N>> {
+        if !self.distances.contains_key(goal) {
+            return vec![];
+        }
+
+        if goal == &self.start {
+            return vec![vec![goal.clone()]];
+        }
+
+        let mut paths = vec![];
+
+        for previous in self.predecessors(goal) {
+            for mut path in self.all_paths(previous) {
+                path.push(goal.clone());
+                paths.push(path);
+            }
+        }
+
+        paths
+    }
+
+    /// The number of shortest paths from the start to `goal`, without building each one
+    pub fn path_count(&self, goal: &N) -> usize {
+        fn count<N: Eq + Hash + Clone>(
+            result: &SearchResult<N>,
+            node: &N,
+            memo: &mut HashMap<N, usize>,
+        ) -> usize {
+            if node == &result.start {
+                return 1;
+            }
+
+            if let Some(&known) = memo.get(node) {
+                return known;
+            }
+
+            let total = result
+                .predecessors(node)
+                .iter()
+                .map(|previous| count(result, previous, memo))
+                .sum();
+
+            memo.insert(node.clone(), total);
+
+            total
+        }
+
+        if !self.distances.contains_key(goal) {
+            return 0;
+        }
+
+        count(self, goal, &mut HashMap::new())
+    }
+}
+
+/// Breadth-first search from `start`, where every step costs 1
+pub fn bfs<N, I>(start: N, mut neighbours: impl FnMut(&N) -> I) -> SearchResult<N>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut result = SearchResult::new(start.clone());
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        let distance = result.distances[&node] + 1;
+
+        for next in neighbours(&node) {
+            if result.relax(&node, next.clone(), distance) {
+                queue.push_back(next);
+            }
+        }
+    }
+
+    result
+}
+
+/// Dijkstra's algorithm from `start`, where `neighbours` gives the cost of each step. Costs must be
+/// greater than zero.
+pub fn dijkstra<N, I>(start: N, neighbours: impl FnMut(&N) -> I) -> SearchResult<N>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = (N, usize)>,
+{
+    best_first(start, None, neighbours, |_| 0)
+}
+
+/**
+A* search from `start` towards `goal`. The heuristic must never overestimate the remaining cost,
+or the paths found may not be the shortest. The search carries on past the goal only as far as is
+needed to find every equally short path to it, so nodes further away may be missing from the
+result.
+*/
+pub fn astar<N, I>(
+    start: N,
+    goal: &N,
+    neighbours: impl FnMut(&N) -> I,
+    heuristic: impl FnMut(&N) -> usize,
+) -> SearchResult<N>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = (N, usize)>,
+{
+    best_first(start, Some(goal), neighbours, heuristic)
+}
+
+/// An entry in the frontier, ordered so that the lowest estimate is popped first
+struct Frontier<N> {
+    estimate: usize,
+    distance: usize,
+    node: N,
+}
+
+impl<N> PartialEq for Frontier<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.estimate == other.estimate
+    }
+}
+
+impl<N> Eq for Frontier<N> {}
+
+impl<N> Ord for Frontier<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.estimate.cmp(&self.estimate)
+    }
+}
+
+impl<N> PartialOrd for Frontier<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn best_first<N, I>(
+    start: N,
+    goal: Option<&N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> usize,
+) -> SearchResult<N>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = (N, usize)>,
+{
+    let mut result = SearchResult::new(start.clone());
+    let mut frontier = BinaryHeap::from([Frontier {
+        estimate: heuristic(&start),
+        distance: 0,
+        node: start,
+    }]);
+
+    while let Some(Frontier {
+        estimate,
+        distance,
+        node,
+    }) = frontier.pop()
+    {
+        // Skip stale entries for nodes that have since been reached more cheaply
+        if distance > result.distances[&node] {
+            continue;
+        }
+
+        if let Some(best) = goal.and_then(|goal| result.distance(goal)) {
+            if estimate > best {
+                break;
+            }
+        }
+
+        for (next, cost) in neighbours(&node) {
+            let next_distance = distance + cost;
+
+            if result.relax(&node, next.clone(), next_distance) {
+                frontier.push(Frontier {
+                    estimate: next_distance + heuristic(&next),
+                    distance: next_distance,
+                    node: next,
+                });
+            }
+        }
+    }
+
+    result
+}
+
+/**
+Neighbour function for searching a grid in orthogonal steps, where a step is allowed if
+`can_move` holds for the contents of the cell being left and the cell being entered.
+*/
+pub fn grid_moves<'a, T>(
+    grid: &'a Grid<T>,
+    can_move: impl Fn(&T, &T) -> bool + 'a,
+) -> impl FnMut(&Vector2di) -> Vec<Vector2di> + 'a {
+    move |&position| {
+        let Some(from) = grid.get(position) else {
+            return vec![];
+        };
+
+        grid.safe_ortho_neighbours(position)
+            .into_iter()
+            .filter(|(_, to)| can_move(from, to))
+            .map(|(next, _)| next)
+            .collect()
+    }
+}
+
+/// Neighbour function for searching a grid in orthogonal steps between passable cells
+pub fn grid_neighbours<'a, T>(
+    grid: &'a Grid<T>,
+    passable: impl Fn(&T) -> bool + 'a,
+) -> impl FnMut(&Vector2di) -> Vec<Vector2di> + 'a {
+    grid_moves(grid, move |_, to| passable(to))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{astar, bfs, dijkstra, grid_moves, grid_neighbours};
+    use crate::common::{Grid, Vector2di};
+
+    const MAZE: &str = "\
+..#.
+#...
+..#.
+.#..";
+
+    fn v(x: i64, y: i64) -> Vector2di {
+        Vector2di::new(x, y)
+    }
+
+    #[test]
+    fn test_bfs() {
+        let grid: Grid<char> = MAZE.parse().unwrap();
+        let result = bfs(v(0, 0), grid_neighbours(&grid, |&c| c == '.'));
+
+        assert_eq!(result.distance(&v(3, 3)), Some(6));
+        assert_eq!(result.distance(&v(0, 3)), Some(5));
+        assert_eq!(result.distance(&v(2, 0)), None);
+
+        let path = result.path(&v(0, 3)).unwrap();
+        assert_eq!(
+            path,
+            vec![v(0, 0), v(1, 0), v(1, 1), v(1, 2), v(0, 2), v(0, 3)]
+        );
+        assert!(result.path(&v(2, 0)).is_none());
+    }
+
+    #[test]
+    fn test_all_paths() {
+        let grid = Grid::try_from(vec![vec![0; 3]; 3]).unwrap();
+        let result = bfs(v(0, 0), grid_neighbours(&grid, |_| true));
+
+        // Corner to corner of a 3x3 grid is 2 steps right and 2 steps down in any order
+        let paths = result.all_paths(&v(2, 2));
+        assert_eq!(paths.len(), 6);
+        assert!(paths.iter().all(|path| path.len() == 5));
+        assert_eq!(result.path_count(&v(2, 2)), 6);
+
+        assert_eq!(result.predecessors(&v(1, 1)).len(), 2);
+        assert_eq!(result.all_paths(&v(0, 0)), vec![vec![v(0, 0)]]);
+        assert_eq!(result.path_count(&v(3, 3)), 0);
+    }
+
+    #[test]
+    fn test_grid_moves() {
+        let grid = Grid::try_from(vec![vec![0, 1, 2], vec![1, 1, 3]]).unwrap();
+        let result = bfs(v(0, 0), grid_moves(&grid, |from, to| *to == from + 1));
+
+        assert_eq!(result.distance(&v(2, 1)), Some(3));
+        assert_eq!(result.path_count(&v(2, 1)), 1);
+        assert_eq!(result.distance(&v(0, 1)), Some(1));
+        assert_eq!(result.distance(&v(1, 1)), None);
+        assert_eq!(result.distances().len(), 5);
+    }
+
+    #[test]
+    fn test_dijkstra_and_astar() {
+        // Each cell costs its value to enter
+        let grid = Grid::try_from(vec![vec![1, 9, 1], vec![1, 9, 1], vec![1, 1, 1]]).unwrap();
+
+        let neighbours = |position: &Vector2di| {
+            grid.safe_ortho_neighbours(*position)
+                .into_iter()
+                .map(|(next, &cost)| (next, cost))
+                .collect::<Vec<_>>()
+        };
+
+        let result = dijkstra(v(0, 0), neighbours);
+        assert_eq!(result.distance(&v(2, 0)), Some(6));
+        assert_eq!(result.distance(&v(1, 0)), Some(9));
+        assert_eq!(result.path(&v(2, 0)).unwrap().len(), 7);
+
+        let manhattan = |position: &Vector2di| (2 - position.x + position.y) as usize;
+        let result = astar(v(0, 0), &v(2, 0), neighbours, manhattan);
+        assert_eq!(result.distance(&v(2, 0)), Some(6));
+        assert_eq!(result.path_count(&v(2, 0)), 1);
+    }
+}
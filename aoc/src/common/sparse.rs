@@ -0,0 +1,426 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Write};
+
+use crate::common::regions::{BoundingBox, Connectivity, RegionInfo};
+use crate::common::{Direction8, Grid, Region, Vector2di};
+
+/**
+A grid that only stores the cells that have something in them, so it has no fixed size and
+positions can be anywhere, including negative. The bounding box grows and shrinks to fit the
+cells as they are inserted and removed.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Vector2di, T>,
+    bounds: Option<BoundingBox>,
+    empty: char,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self {
+            cells: HashMap::new(),
+            bounds: None,
+            empty: '.',
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The same grid, displayed with `empty` in the positions with nothing in them
+    pub fn with_empty(mut self, empty: char) -> Self {
+        self.empty = empty;
+        self
+    }
+
+    /// The number of cells with something in them
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The smallest rectangle containing every cell, or `None` if there aren't any
+    pub fn bounds(&self) -> Option<BoundingBox> {
+        self.bounds
+    }
+
+    pub fn contains(&self, loc: impl Into<Vector2di>) -> bool {
+        self.cells.contains_key(&loc.into())
+    }
+
+    pub fn get(&self, loc: impl Into<Vector2di>) -> Option<&T> {
+        self.cells.get(&loc.into())
+    }
+
+    pub fn get_mut(&mut self, loc: impl Into<Vector2di>) -> Option<&mut T> {
+        self.cells.get_mut(&loc.into())
+    }
+
+    /// Put `value` at the given position, returning whatever was there before
+    pub fn insert(&mut self, loc: impl Into<Vector2di>, value: T) -> Option<T> {
+        let idx = loc.into();
+
+        match &mut self.bounds {
+            Some(bounds) => bounds.extend(idx),
+            None => self.bounds = Some(BoundingBox::around(idx)),
+        }
+
+        self.cells.insert(idx, value)
+    }
+
+    /// Empty the cell at the given position, returning what was in it
+    pub fn remove(&mut self, loc: impl Into<Vector2di>) -> Option<T> {
+        let idx = loc.into();
+        let removed = self.cells.remove(&idx)?;
+
+        // Only a cell on the edge of the bounding box can make it smaller
+        let bounds = self.bounds.unwrap();
+        let on_edge = idx.x == bounds.min.x
+            || idx.x == bounds.max.x
+            || idx.y == bounds.min.y
+            || idx.y == bounds.max.y;
+
+        if on_edge {
+            self.bounds = self.cells.keys().fold(None, |bounds, &position| {
+                let mut bounds = bounds.unwrap_or(BoundingBox::around(position));
+                bounds.extend(position);
+                Some(bounds)
+            });
+        }
+
+        Some(removed)
+    }
+
+    /// The position and contents of every cell with something in it, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (Vector2di, &T)> + '_ {
+        self.cells
+            .iter()
+            .map(|(&position, contents)| (position, contents))
+    }
+
+    /// The positions of every cell with something in it, in no particular order
+    pub fn positions(&self) -> impl Iterator<Item = Vector2di> + '_ {
+        self.cells.keys().copied()
+    }
+
+    /// The orthogonal neighbours of a position that have something in them
+    pub fn safe_ortho_neighbours(&self, loc: impl Into<Vector2di>) -> Vec<(Vector2di, &T)> {
+        let idx = loc.into();
+
+        [Direction8::N, Direction8::E, Direction8::S, Direction8::W]
+            .into_iter()
+            .map(|direction| idx + direction.into())
+            .filter_map(|n| Some((n, self.get(n)?)))
+            .collect()
+    }
+
+    /// The neighbours of a position in all eight directions that have something in them,
+    /// clockwise from north
+    pub fn neighbours(&self, loc: impl Into<Vector2di>) -> Vec<(Vector2di, &T)> {
+        let idx = loc.into();
+
+        Direction8::ALL
+            .into_iter()
+            .map(|direction| idx + direction.into())
+            .filter_map(|n| Some((n, self.get(n)?)))
+            .collect()
+    }
+
+    pub fn neighbour(&self, loc: impl Into<Vector2di>, direction: Direction8) -> Option<&T> {
+        self.get(loc.into() + direction.into())
+    }
+
+    fn connected(&self, loc: Vector2di, connectivity: Connectivity) -> Vec<(Vector2di, &T)> {
+        match connectivity {
+            Connectivity::Four => self.safe_ortho_neighbours(loc),
+            Connectivity::Eight => self.neighbours(loc),
+        }
+    }
+
+    /// Flood fill outwards from `start` like a dense grid does, but only through cells that have
+    /// something in them
+    fn flood(
+        &self,
+        start: Vector2di,
+        connectivity: Connectivity,
+        same: &impl Fn(&T, &T) -> bool,
+        visited: &mut HashSet<Vector2di>,
+    ) -> Vec<Vector2di> {
+        if !self.contains(start) {
+            return vec![];
+        }
+
+        visited.insert(start);
+
+        let mut cells = vec![start];
+        let mut buffer = vec![start];
+
+        while let Some(position) = buffer.pop() {
+            let contents = &self.cells[&position];
+
+            for (neighbour, other) in self.connected(position, connectivity) {
+                if !visited.contains(&neighbour) && same(contents, other) {
+                    visited.insert(neighbour);
+                    cells.push(neighbour);
+                    buffer.push(neighbour);
+                }
+            }
+        }
+
+        cells
+    }
+}
+
+impl<T: PartialEq> SparseGrid<T> {
+    /// The orthogonally connected cells with the same contents as the cell at `loc`
+    pub fn region(&self, loc: impl Into<Vector2di>) -> Region {
+        self.flood(
+            loc.into(),
+            Connectivity::Four,
+            &|a, b| a == b,
+            &mut HashSet::new(),
+        )
+    }
+}
+
+impl<T: Clone> SparseGrid<T> {
+    /**
+    Split the cells with something in them into connected regions, like [`Grid::label_regions_by`].
+    Empty positions are never part of a region, so they also separate regions.
+    */
+    pub fn label_regions_by(
+        &self,
+        connectivity: Connectivity,
+        same: impl Fn(&T, &T) -> bool,
+    ) -> SparseLabelling<T> {
+        let mut positions: Vec<Vector2di> = self.positions().collect();
+        positions.sort_by_key(|position| (position.y, position.x));
+
+        let mut visited = HashSet::new();
+        let mut labels = SparseGrid::new();
+        let mut regions = vec![];
+
+        for start in positions {
+            if visited.contains(&start) {
+                continue;
+            }
+
+            let cells = self.flood(start, connectivity, &same, &mut visited);
+
+            let mut bounds = BoundingBox::around(start);
+
+            for &cell in &cells {
+                labels.insert(cell, regions.len());
+                bounds.extend(cell);
+            }
+
+            regions.push(RegionInfo {
+                id: regions.len(),
+                contents: self.cells[&start].clone(),
+                cells,
+                bounds,
+            });
+        }
+
+        SparseLabelling { labels, regions }
+    }
+
+    /**
+    Copy the cells into a dense grid covering the bounding box, with `empty` in the positions that
+    have nothing in them. The top-left corner of the bounding box is at `(0, 0)` in the new grid.
+    */
+    pub fn to_grid(&self, empty: T) -> Grid<T> {
+        let Some(bounds) = self.bounds else {
+            return Grid::filled(0, 0, empty);
+        };
+
+        let mut grid = Grid::filled(bounds.width(), bounds.height(), empty);
+
+        for (&position, contents) in &self.cells {
+            grid[position - bounds.min] = contents.clone();
+        }
+
+        grid
+    }
+
+    /// The cells of a dense grid where `keep` holds for their contents, at the same positions
+    pub fn from_grid(grid: &Grid<T>, keep: impl Fn(&T) -> bool) -> Self {
+        let mut sparse = Self::new();
+
+        for (position, contents) in grid.iter().filter(|(_, contents)| keep(contents)) {
+            sparse.insert(position, contents.clone());
+        }
+
+        sparse
+    }
+}
+
+impl<T: Clone + PartialEq> SparseGrid<T> {
+    /// Split the cells with something in them into connected regions of cells with equal contents
+    pub fn label_regions(&self, connectivity: Connectivity) -> SparseLabelling<T> {
+        self.label_regions_by(connectivity, |a, b| a == b)
+    }
+}
+
+impl<T> From<Grid<Option<T>>> for SparseGrid<T> {
+    fn from(value: Grid<Option<T>>) -> Self {
+        let mut sparse = Self::new();
+
+        for cell in value {
+            if let Some(contents) = cell.contents {
+                sparse.insert((cell.x, cell.y), contents);
+            }
+        }
+
+        sparse
+    }
+}
+
+impl<T: Copy + Into<char>> Display for SparseGrid<T> {
+    /// Draw the bounding box, with the empty glyph wherever there is nothing
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Some(bounds) = self.bounds else {
+            return Ok(());
+        };
+
+        for y in bounds.min.y..=bounds.max.y {
+            for x in bounds.min.x..=bounds.max.x {
+                let c = self
+                    .get((x, y))
+                    .map_or(self.empty, |&contents| contents.into());
+
+                f.write_char(c)?;
+            }
+
+            f.write_str("\n")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Every cell of a sparse grid labelled with the region it belongs to
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparseLabelling<T> {
+    pub labels: SparseGrid<usize>,
+    pub regions: Vec<RegionInfo<T>>,
+}
+
+impl<T> SparseLabelling<T> {
+    pub fn label(&self, loc: impl Into<Vector2di>) -> Option<usize> {
+        self.labels.get(loc).copied()
+    }
+
+    pub fn region_at(&self, loc: impl Into<Vector2di>) -> Option<&RegionInfo<T>> {
+        Some(&self.regions[self.label(loc)?])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SparseGrid;
+    use crate::common::regions::Connectivity;
+    use crate::common::{Direction8, Grid, Vector2di};
+
+    #[test]
+    fn test_insert_and_bounds() {
+        let mut sparse = SparseGrid::new();
+        assert!(sparse.bounds().is_none());
+        assert_eq!(sparse.to_string(), "");
+
+        sparse.insert((2, 1), 'a');
+        sparse.insert((-1, 3), 'b');
+        assert_eq!(sparse.insert((2, 1), 'c'), Some('a'));
+
+        let bounds = sparse.bounds().unwrap();
+        assert_eq!(
+            (bounds.min, bounds.max),
+            (Vector2di::new(-1, 1), Vector2di::new(2, 3))
+        );
+        assert_eq!(sparse.len(), 2);
+        assert_eq!(sparse.to_string(), "...c\n....\nb...\n");
+        assert_eq!(
+            sparse.clone().with_empty(' ').to_string(),
+            "   c\n    \nb   \n"
+        );
+
+        assert_eq!(sparse.remove((-1, 3)), Some('b'));
+        assert_eq!(sparse.remove((-1, 3)), None);
+        assert_eq!(sparse.bounds().unwrap().min, Vector2di::new(2, 1));
+        assert_eq!(sparse.to_string(), "c\n");
+
+        sparse.remove((2, 1));
+        assert!(sparse.is_empty());
+        assert!(sparse.bounds().is_none());
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let mut sparse = SparseGrid::new();
+
+        for (position, c) in [((0, 0), 'a'), ((1, 0), 'b'), ((1, 1), 'c'), ((-5, -5), 'd')] {
+            sparse.insert(position, c);
+        }
+
+        let ortho: String = sparse
+            .safe_ortho_neighbours((0, 0))
+            .iter()
+            .map(|(_, &c)| c)
+            .collect();
+        assert_eq!(ortho, "b");
+
+        let all: String = sparse.neighbours((0, 0)).iter().map(|(_, &c)| c).collect();
+        assert_eq!(all, "bc");
+
+        assert_eq!(sparse.neighbour((1, 1), Direction8::N), Some(&'b'));
+        assert_eq!(sparse.neighbour((1, 1), Direction8::S), None);
+    }
+
+    #[test]
+    fn test_regions() {
+        let mut sparse = SparseGrid::new();
+
+        for position in [(0, 0), (1, 0), (2, 1), (10, 10), (10, 11)] {
+            sparse.insert(position, '#');
+        }
+
+        let four = sparse.label_regions(Connectivity::Four);
+        assert_eq!(four.regions.len(), 3);
+        assert_eq!(four.label((1, 0)), Some(0));
+        assert_eq!(four.region_at((10, 11)).unwrap().area(), 2);
+        assert_eq!(four.label((5, 5)), None);
+
+        let eight = sparse.label_regions(Connectivity::Eight);
+        assert_eq!(eight.regions.len(), 2);
+        assert_eq!(eight.region_at((2, 1)).unwrap().area(), 3);
+        assert_eq!(eight.regions[0].bounds.width(), 3);
+
+        assert_eq!(sparse.region((10, 10)).len(), 2);
+        assert!(sparse.region((5, 5)).is_empty());
+    }
+
+    #[test]
+    fn test_dense_conversion() {
+        let grid: Grid<char> = ".#.\n..#".parse().unwrap();
+
+        let sparse = SparseGrid::from_grid(&grid, |&c| c == '#');
+        assert_eq!(sparse.len(), 2);
+        assert_eq!(sparse.get((2, 1)), Some(&'#'));
+
+        // The dense copy only covers the bounding box
+        assert_eq!(sparse.to_grid('.').to_string(), "#.\n.#\n");
+        assert_eq!(SparseGrid::<char>::new().to_grid('.').width(), 0);
+
+        let options = Grid::try_from(vec![vec![None, Some(1)], vec![Some(2), None]]).unwrap();
+        let sparse = SparseGrid::from(options);
+        assert_eq!(sparse.len(), 2);
+        assert_eq!(sparse.get((0, 1)), Some(&2));
+    }
+}
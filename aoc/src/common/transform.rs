@@ -0,0 +1,235 @@
+use std::fmt::{Display, Write};
+
+use crate::common::{Grid, Topology, Vector2di};
+
+impl<T: Clone> Grid<T> {
+    /// Build a new grid where the cell at each position is copied from `source(x, y)`
+    fn remap(
+        &self,
+        width: usize,
+        height: usize,
+        source: impl Fn(usize, usize) -> (usize, usize),
+    ) -> Self {
+        let mut cells = Vec::with_capacity(width * height);
+
+        for y in 0..height {
+            for x in 0..width {
+                let (sx, sy) = source(x, y);
+
+                cells.push(self.cells[sy * self.width + sx].clone());
+            }
+        }
+
+        Self {
+            width,
+            height,
+            cells,
+            topology: self.topology,
+        }
+    }
+
+    /// Swap rows and columns, mirroring the grid along the diagonal from the top-left corner
+    pub fn transpose(&self) -> Self {
+        self.remap(self.height, self.width, |x, y| (y, x))
+    }
+
+    /// Mirror the grid left to right
+    pub fn flip_horizontal(&self) -> Self {
+        self.remap(self.width, self.height, |x, y| (self.width - 1 - x, y))
+    }
+
+    /// Mirror the grid top to bottom
+    pub fn flip_vertical(&self) -> Self {
+        self.remap(self.width, self.height, |x, y| (x, self.height - 1 - y))
+    }
+
+    /// Rotate the grid clockwise by 90 degrees for each turn. Negative turns rotate anticlockwise.
+    pub fn rotate(&self, turns: i32) -> Self {
+        let (width, height) = (self.width, self.height);
+
+        match turns.rem_euclid(4) {
+            0 => self.clone(),
+            1 => self.remap(height, width, |x, y| (y, height - 1 - x)),
+            2 => self.remap(width, height, |x, y| (width - 1 - x, height - 1 - y)),
+            _ => self.remap(height, width, |x, y| (width - 1 - y, x)),
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /**
+    Borrow the rectangle of the grid with its top-left corner at `origin`. Returns `None` if any
+    of the rectangle is outside the grid.
+    */
+    pub fn view(
+        &self,
+        origin: impl Into<Vector2di>,
+        width: usize,
+        height: usize,
+    ) -> Option<GridView<'_, T>> {
+        let origin = origin.into();
+
+        let (x, y): (usize, usize) = origin.try_into().ok()?;
+
+        if x + width > self.width || y + height > self.height {
+            return None;
+        }
+
+        Some(GridView {
+            grid: self,
+            origin,
+            width,
+            height,
+        })
+    }
+}
+
+/**
+A rectangle borrowed from a grid. Positions in the view are relative to its top-left corner, so
+`(0, 0)` is the view's `origin` in the grid.
+*/
+#[derive(Debug, Clone, Copy)]
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    origin: Vector2di,
+    width: usize,
+    height: usize,
+}
+
+impl<'a, T> GridView<'a, T> {
+    pub fn origin(&self) -> Vector2di {
+        self.origin
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, loc: impl Into<Vector2di>) -> bool {
+        <(usize, usize)>::try_from(loc.into()).is_ok_and(|(x, y)| x < self.width && y < self.height)
+    }
+
+    pub fn get(&self, loc: impl Into<Vector2di>) -> Option<&'a T> {
+        let idx = loc.into();
+
+        if !self.contains(idx) {
+            return None;
+        }
+
+        self.grid.get(self.origin + idx)
+    }
+
+    /// Each row of the view from top to bottom, as a slice of the underlying grid
+    pub fn rows(&self) -> impl Iterator<Item = &'a [T]> + '_ {
+        let grid = self.grid;
+        let (x, top): (usize, usize) = self.origin.try_into().unwrap();
+
+        (0..self.height).map(move |y| {
+            let start = (top + y) * grid.width + x;
+
+            &grid.cells[start..start + self.width]
+        })
+    }
+
+    /// The position in the view and contents of every cell, in row-major order
+    pub fn iter(&self) -> impl Iterator<Item = (Vector2di, &'a T)> + '_ {
+        self.rows().enumerate().flat_map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .map(move |(x, contents)| ((x, y).into(), contents))
+        })
+    }
+
+    /// Copy the view into a grid of its own
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.rows().flatten().cloned().collect(),
+            topology: Topology::Bounded,
+        }
+    }
+}
+
+impl<T: Copy + Into<char>> Display for GridView<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for contents in row {
+                f.write_char((*contents).into())?;
+            }
+
+            f.write_str("\n")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::common::{Grid, Vector2di};
+
+    // abc
+    // def
+    #[test]
+    fn test_transpose_and_flip() {
+        let grid: Grid<char> = "abc\ndef".parse().unwrap();
+
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.transpose().transpose(), grid);
+        assert_eq!(grid.flip_horizontal().to_string(), "cba\nfed\n");
+        assert_eq!(grid.flip_vertical().to_string(), "def\nabc\n");
+    }
+
+    #[test]
+    fn test_rotate() {
+        let grid: Grid<char> = "abc\ndef".parse().unwrap();
+
+        assert_eq!(grid.rotate(1).to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotate(2).to_string(), "fed\ncba\n");
+        assert_eq!(grid.rotate(3).to_string(), "cf\nbe\nad\n");
+        assert_eq!(grid.rotate(-1), grid.rotate(3));
+        assert_eq!(grid.rotate(4), grid);
+        assert_eq!(grid.rotate(1).rotate(1), grid.rotate(2));
+
+        // A half turn is the same as flipping both ways
+        assert_eq!(grid.rotate(2), grid.flip_horizontal().flip_vertical());
+
+        let rotated = grid.rotate(1);
+        assert_eq!((rotated.width(), rotated.height()), (2, 3));
+    }
+
+    #[test]
+    fn test_view() {
+        let grid = Grid::try_from(vec![
+            "abcd".chars().collect(),
+            "efgh".chars().collect(),
+            "ijkl".chars().collect(),
+        ])
+        .unwrap();
+
+        let view = grid.view((1, 1), 2, 2).unwrap();
+
+        assert_eq!(view.to_string(), "fg\njk\n");
+        assert_eq!(view.get((1, 0)), Some(&'g'));
+        assert_eq!(view.get((2, 0)), None);
+        assert_eq!(view.origin(), Vector2di::new(1, 1));
+
+        let cells: Vec<(Vector2di, char)> = view.iter().map(|(p, &c)| (p, c)).collect();
+        assert_eq!(cells[2], (Vector2di::new(0, 1), 'j'));
+
+        assert_eq!(view.to_grid().transpose().to_string(), "fj\ngk\n");
+
+        assert!(grid.view((3, 0), 2, 1).is_none());
+        assert!(grid.view((0, -1), 1, 1).is_none());
+        assert_eq!(grid.view((0, 0), 4, 3).unwrap().to_grid(), grid);
+        assert_eq!(grid.view((4, 3), 0, 0).unwrap().iter().count(), 0);
+    }
+}
@@ -0,0 +1,25 @@
+#![allow(clippy::pedantic)]
+
+pub mod answers;
+pub mod bench;
+pub mod common;
+pub mod registry;
+pub mod runner;
+pub mod scaffold;
+pub mod solution;
+
+pub mod problem1;
+pub mod problem10;
+pub mod problem11;
+pub mod problem12;
+pub mod problem13;
+pub mod problem14;
+pub mod problem15;
+pub mod problem2;
+pub mod problem3;
+pub mod problem4;
+pub mod problem5;
+pub mod problem6;
+pub mod problem7;
+pub mod problem8;
+pub mod problem9;
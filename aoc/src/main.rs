@@ -1,37 +1,348 @@
 #![allow(clippy::pedantic)]
 
-use std::{
-    fs::File,
-    io::{self, BufRead},
-};
-
-pub mod common;
-
-// pub mod problem1;
-// pub mod problem2;
-// pub mod problem3;
-// pub mod problem4;
-// pub mod problem5;
-// pub mod problem6;
-// pub mod problem7;
-// pub mod problem8;
-// pub mod problem9;
-// pub mod problem10;
-// pub mod problem11;
-// pub mod problem12;
-// pub mod problem13;
-// pub mod problem14;
-pub mod problem15;
-
-fn main() -> io::Result<()> {
-    let f = File::open("inputs/15.txt")?;
-    let lines: Vec<String> = io::BufReader::new(f).lines().map(|l| l.unwrap()).collect();
-
-    let input = problem15::Input::from(lines);
-    let solution = problem15::solution(input);
-    // let solution = problem14::solution_part_two(input);
-
-    println!("Solution {:?}", solution);
+use std::{env, process};
+
+use aoc::answers::{self, Answers};
+use aoc::runner::{self, Format, Status};
+use aoc::solution::Part;
+use aoc::{bench, scaffold};
+
+const USAGE: &str = "\
+usage: aoc <day> [--part 1|2] [--input path|-] [--format text|json]
+       aoc all [--format text|json]
+       aoc verify [--format text|json]
+       aoc bench <day> [--part 1|2] [--input path|-] [--runs n] [--warmup n]
+       aoc new <day>";
+
+const DEFAULT_RUNS: usize = 10;
+const DEFAULT_WARMUP: usize = 2;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Command {
+    Run {
+        day: usize,
+        part: Option<Part>,
+        input: Option<String>,
+        format: Format,
+    },
+    All {
+        format: Format,
+    },
+    Verify {
+        format: Format,
+    },
+    Bench {
+        day: usize,
+        part: Option<Part>,
+        input: Option<String>,
+        runs: usize,
+        warmup: usize,
+    },
+    New {
+        day: usize,
+    },
+}
+
+#[derive(Debug, Default)]
+struct Options {
+    day: Option<usize>,
+    part: Option<Part>,
+    input: Option<String>,
+    runs: Option<usize>,
+    warmup: Option<usize>,
+    format: Option<Format>,
+    /// The names of the options that were given, so commands can reject those they don't use
+    given: Vec<String>,
+}
+
+impl Options {
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut options = Options::default();
+        let mut iter = args.into_iter();
+
+        let count = |option: &str, value: Option<String>| -> Result<usize, String> {
+            let value = value.ok_or(format!("{} requires a value", option))?;
+
+            value
+                .parse()
+                .map_err(|_| format!("invalid value '{}' for {}", value, option))
+        };
+
+        while let Some(arg) = iter.next() {
+            match arg.as_str() {
+                "--part" => {
+                    let value = iter.next().ok_or("--part requires a value")?;
+                    options.part = Some(Part::try_from(value.as_str())?);
+                }
+                "--input" => {
+                    options.input = Some(iter.next().ok_or("--input requires a path")?);
+                }
+                "--runs" => options.runs = Some(count("--runs", iter.next())?),
+                "--warmup" => options.warmup = Some(count("--warmup", iter.next())?),
+                "--format" => {
+                    let value = iter.next().ok_or("--format requires a value")?;
+                    options.format = Some(Format::try_from(value.as_str())?);
+                }
+                _ if arg.starts_with("--") => return Err(format!("unknown option '{}'", arg)),
+                _ if options.day.is_none() => {
+                    options.day = Some(
+                        arg.parse()
+                            .map_err(|_| format!("invalid day '{}', expected a number", arg))?,
+                    );
+                    continue;
+                }
+                _ => return Err(format!("unexpected argument '{}'", arg)),
+            }
+
+            options.given.push(arg);
+        }
+
+        Ok(options)
+    }
+
+    /// Fail if an option was given that the command doesn't accept
+    fn only(&self, command: &str, accepted: &[&str]) -> Result<(), String> {
+        match self
+            .given
+            .iter()
+            .find(|option| !accepted.contains(&option.as_str()))
+        {
+            Some(option) => Err(format!("{} can't be used with {}", option, command)),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Command {
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut iter = args.into_iter().peekable();
+
+        let name = iter.next_if(|arg| ["all", "verify", "bench", "new"].contains(&arg.as_str()));
+        let options = Options::parse(iter)?;
+
+        match name.as_deref() {
+            Some(name @ ("all" | "verify")) => {
+                if let Some(day) = options.day {
+                    return Err(format!("unexpected argument '{}'", day));
+                }
+
+                options.only(name, &["--format"])?;
+
+                let format = options.format.unwrap_or_default();
+
+                Ok(if name == "all" {
+                    Command::All { format }
+                } else {
+                    Command::Verify { format }
+                })
+            }
+            Some("new") => {
+                options.only("new", &[])?;
+
+                Ok(Command::New {
+                    day: options.day.ok_or("missing day")?,
+                })
+            }
+            Some(name) => {
+                options.only(name, &["--part", "--input", "--runs", "--warmup"])?;
+
+                Ok(Command::Bench {
+                    day: options.day.ok_or("missing day")?,
+                    part: options.part,
+                    input: options.input,
+                    runs: options.runs.unwrap_or(DEFAULT_RUNS),
+                    warmup: options.warmup.unwrap_or(DEFAULT_WARMUP),
+                })
+            }
+            None => {
+                options.only("a single day", &["--part", "--input", "--format"])?;
+
+                Ok(Command::Run {
+                    day: options.day.ok_or("missing day")?,
+                    part: options.part,
+                    input: options.input,
+                    format: options.format.unwrap_or_default(),
+                })
+            }
+        }
+    }
+}
+
+fn print_outcomes(outcomes: &[runner::Outcome], format: Format) {
+    for outcome in outcomes {
+        match (format, &outcome.status) {
+            (Format::Json, _) => println!("{}", runner::format_json(outcome, None)),
+            (Format::Text, Status::Solved(answer)) => {
+                println!("Day {} part {}: {}", outcome.day, outcome.part, answer)
+            }
+            (Format::Text, _) => {}
+        }
+    }
+}
+
+fn run() -> Result<(), String> {
+    let command = Command::parse(env::args().skip(1)).map_err(|e| format!("{}\n{}", e, USAGE))?;
+
+    match command {
+        Command::Run {
+            day,
+            part,
+            input,
+            format,
+        } => print_outcomes(&runner::run_day(day, part, input.as_deref())?, format),
+        Command::All { format } => {
+            let outcomes = runner::run_all();
+
+            match format {
+                Format::Text => print!("{}", runner::format_table(&outcomes)),
+                Format::Json => print_outcomes(&outcomes, format),
+            }
+        }
+        Command::Verify { format } => {
+            let checks = answers::verify(&Answers::load_default()?);
+
+            match format {
+                Format::Text => print!("{}", runner::format_verification(&checks)),
+                Format::Json => {
+                    for (outcome, verdict) in &checks {
+                        println!("{}", runner::format_json(outcome, Some(verdict)));
+                    }
+                }
+            }
+
+            if answers::any_failed(&checks) {
+                process::exit(1);
+            }
+        }
+        Command::Bench {
+            day,
+            part,
+            input,
+            runs,
+            warmup,
+        } => {
+            for (part, report) in bench::bench_day(day, part, input.as_deref(), warmup, runs)? {
+                println!(
+                    "Day {} part {} ({} runs, {} warm-up)\n{}",
+                    day, part, runs, warmup, report
+                );
+            }
+        }
+        Command::New { day } => scaffold::scaffold(day)?,
+    }
 
     Ok(())
 }
+
+fn main() {
+    if let Err(e) = run() {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Command, Format, Part, DEFAULT_RUNS, DEFAULT_WARMUP};
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            Command::parse(args("4")),
+            Ok(Command::Run {
+                day: 4,
+                part: None,
+                input: None,
+                format: Format::Text
+            })
+        );
+
+        assert_eq!(
+            Command::parse(args("15 --part 1 --input example.txt")),
+            Ok(Command::Run {
+                day: 15,
+                part: Some(Part::One),
+                input: Some("example.txt".to_owned()),
+                format: Format::Text
+            })
+        );
+
+        assert!(Command::parse(args("")).is_err());
+        assert!(Command::parse(args("four")).is_err());
+        assert!(Command::parse(args("4 --part 3")).is_err());
+        assert!(Command::parse(args("4 --input")).is_err());
+        assert!(Command::parse(args("4 5")).is_err());
+
+        assert!(Command::parse(args("4 --runs 5")).is_err());
+    }
+
+    #[test]
+    fn test_parse_format_args() {
+        assert_eq!(
+            Command::parse(args("all")),
+            Ok(Command::All {
+                format: Format::Text
+            })
+        );
+        assert_eq!(
+            Command::parse(args("verify --format json")),
+            Ok(Command::Verify {
+                format: Format::Json
+            })
+        );
+        assert_eq!(
+            Command::parse(args("3 --format json")),
+            Ok(Command::Run {
+                day: 3,
+                part: None,
+                input: None,
+                format: Format::Json
+            })
+        );
+
+        assert!(Command::parse(args("all 4")).is_err());
+        assert!(Command::parse(args("all --part 1")).is_err());
+        assert!(Command::parse(args("verify --format xml")).is_err());
+        assert!(Command::parse(args("bench 3 --format json")).is_err());
+    }
+
+    #[test]
+    fn test_parse_new_args() {
+        assert_eq!(Command::parse(args("new 16")), Ok(Command::New { day: 16 }));
+        assert!(Command::parse(args("new")).is_err());
+        assert!(Command::parse(args("new 16 --part 1")).is_err());
+    }
+
+    #[test]
+    fn test_parse_bench_args() {
+        assert_eq!(
+            Command::parse(args("bench 6 --part 2 --runs 5 --warmup 0")),
+            Ok(Command::Bench {
+                day: 6,
+                part: Some(Part::Two),
+                input: None,
+                runs: 5,
+                warmup: 0
+            })
+        );
+
+        assert_eq!(
+            Command::parse(args("bench 11")),
+            Ok(Command::Bench {
+                day: 11,
+                part: None,
+                input: None,
+                runs: DEFAULT_RUNS,
+                warmup: DEFAULT_WARMUP
+            })
+        );
+
+        assert!(Command::parse(args("bench")).is_err());
+        assert!(Command::parse(args("bench 11 --runs")).is_err());
+        assert!(Command::parse(args("bench 11 --runs many")).is_err());
+    }
+}
@@ -1,3 +1,5 @@
+use crate::solution::{Solution, Solver};
+
 pub struct Input {
     left_list: Vec<usize>,
     right_list: Vec<usize>,
@@ -49,3 +51,17 @@ pub fn parse_lines(lines: Vec<String>) -> Input {
         right_list: right,
     }
 }
+
+pub struct Problem;
+
+impl Solution for Problem {
+    type Input = Input;
+
+    fn parse(lines: Vec<String>) -> Input {
+        parse_lines(lines)
+    }
+
+    const PART_ONE: Solver<Input> = Some(|input| solution(input).to_string());
+
+    const PART_TWO: Solver<Input> = Some(|input| solution_part_two(input).to_string());
+}
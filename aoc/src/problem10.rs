@@ -1,6 +1,6 @@
-use std::collections::VecDeque;
-
-use crate::common::{CellContents, Grid, Vector2D};
+use crate::common::search::{self, SearchResult};
+use crate::common::{CellContents, Grid, Vector2di};
+use crate::solution::{Solution, Solver};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 struct Step {
@@ -31,59 +31,46 @@ impl From<Vec<String>> for Input {
     fn from(value: Vec<String>) -> Self {
         Self {
             map: TopoMap {
-                grid: Grid::new(
-                    value
-                        .iter()
-                        .map(|s| s.chars().collect::<Vec<char>>())
-                        .collect(),
-                ),
+                grid: Grid::from_lines(&value).unwrap().parse_cells(),
             },
         }
     }
 }
 
+/// Search every trail that climbs one step of height at a time from a trailhead
+fn trails(grid: &Grid<Step>, trailhead: &Step) -> SearchResult<Vector2di> {
+    search::bfs(
+        (trailhead.x, trailhead.y).into(),
+        search::grid_moves(grid, |from: &Step, to: &Step| to.height == from.height + 1),
+    )
+}
+
+fn trailheads(grid: &Grid<Step>) -> Vec<Step> {
+    grid.iter()
+        .filter(|(_, step)| step.height == 0)
+        .map(|(_, &step)| step)
+        .collect()
+}
+
+fn peaks<'a>(
+    grid: &'a Grid<Step>,
+    trails: &'a SearchResult<Vector2di>,
+) -> impl Iterator<Item = &'a Vector2di> {
+    trails
+        .distances()
+        .keys()
+        .filter(|&&position| grid[position].height == 9)
+}
+
 pub fn solution(input: Input) -> usize {
     let mut result = 0;
 
     let grid = input.map.grid;
-    let trailheads: Vec<Step> = grid
-        .clone()
-        .into_iter()
-        .filter_map(|cell| {
-            if cell.contents().height == 0 {
-                Some(cell.contents())
-            } else {
-                None
-            }
-        })
-        .collect();
-
-    for trailhead in trailheads {
-        let mut queue: VecDeque<Step> = VecDeque::new();
-        let mut visited_peaks: Vec<Step> = vec![];
-        let mut score = 0;
-
-        queue.push_back(trailhead);
-
-        while !queue.is_empty() {
-            let step = queue.pop_back().unwrap();
-
-            let neighbours = grid.neighbours(Vector2D::new(step.x as i32, step.y as i32));
-
-            for n in neighbours {
-                if step.height == 8 && n.height == 9 && !visited_peaks.contains(&n) {
-                    visited_peaks.push(n);
-                    score += 1;
-                } else if n.height == step.height + 1 {
-                    queue.push_back(n);
-                }
-            }
-        }
 
-        println!(
-            "Trailhead at {}, {} has score {}",
-            trailhead.x, trailhead.y, score
-        );
+    for trailhead in trailheads(&grid) {
+        let trails = trails(&grid, &trailhead);
+        let score = peaks(&grid, &trails).count();
+
         result += score;
     }
 
@@ -94,42 +81,14 @@ pub fn solution_part_two(input: Input) -> usize {
     let mut result = 0;
 
     let grid = input.map.grid;
-    let trailheads: Vec<Step> = grid
-        .clone()
-        .into_iter()
-        .filter_map(|cell| {
-            if cell.contents().height == 0 {
-                Some(cell.contents())
-            } else {
-                None
-            }
-        })
-        .collect();
-
-    for trailhead in trailheads {
-        let mut rating = 0;
-        let mut queue: VecDeque<Step> = VecDeque::new();
-
-        queue.push_back(trailhead);
-
-        while !queue.is_empty() {
-            let step = queue.pop_back().unwrap();
-
-            let neighbours = grid.neighbours(Vector2D::new(step.x as i32, step.y as i32));
-
-            for n in neighbours {
-                if step.height == 8 && n.height == 9 {
-                    rating += 1;
-                } else if n.height == step.height + 1 {
-                    queue.push_back(n);
-                }
-            }
-        }
 
-        println!(
-            "Trailhead at {}, {} has rating {}",
-            trailhead.x, trailhead.y, rating
-        );
+    for trailhead in trailheads(&grid) {
+        let trails = trails(&grid, &trailhead);
+
+        // Every trail to a peak is the same length, so each one is a shortest path
+        let rating: usize = peaks(&grid, &trails)
+            .map(|peak| trails.path_count(peak))
+            .sum();
 
         result += rating;
     }
@@ -137,13 +96,27 @@ pub fn solution_part_two(input: Input) -> usize {
     result
 }
 
+pub struct Problem;
+
+impl Solution for Problem {
+    type Input = Input;
+
+    fn parse(lines: Vec<String>) -> Input {
+        Input::from(lines)
+    }
+
+    const PART_ONE: Solver<Input> = Some(|input| solution(input).to_string());
+
+    const PART_TWO: Solver<Input> = Some(|input| solution_part_two(input).to_string());
+}
+
 #[cfg(test)]
 mod test {
     use crate::problem10::solution_part_two;
 
     use super::{solution, Input};
 
-    const EXAMPLE: &'static str = "\
+    const EXAMPLE: &str = "\
 89010123
 78121874
 87430965
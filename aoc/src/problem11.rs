@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use crate::solution::{Solution, Solver};
+
 #[derive(Debug)]
 pub struct Input {
     stones: Vec<usize>,
@@ -19,9 +21,7 @@ impl From<Vec<String>> for Input {
 fn solve(input: Input, num_blinks: usize) -> usize {
     let mut stones = input.stones;
 
-    for i in 0..num_blinks {
-        println!("Blink {}", i);
-        println!("{}", stones.len());
+    for _ in 0..num_blinks {
         let mut next: Vec<usize> = vec![];
 
         for stone in stones {
@@ -33,10 +33,10 @@ fn solve(input: Input, num_blinks: usize) -> usize {
                 let mut right = stone;
 
                 for _ in 0..(length / 2) {
-                    left = left / 10;
+                    left /= 10;
                 }
 
-                right = right - left * (10 as usize).pow(length as u32 / 2);
+                right -= left * 10_usize.pow(length as u32 / 2);
 
                 next.push(left);
                 next.push(right);
@@ -87,28 +87,28 @@ fn expand(stone: Stone, depth: usize, caches: &mut Caches) -> Length {
             };
 
             caches[depth].insert(stone, result);
-            return result;
+            result
         }
     }
 }
 
 fn step(stone: Stone) -> Vec<Stone> {
     if stone == 0 {
-        return vec![1];
+        vec![1]
     } else if stone.ilog10() % 2 == 1 {
         let length = stone.ilog10() + 1;
 
         let mut left = stone;
 
         for _ in 0..(length / 2) {
-            left = left / 10;
+            left /= 10;
         }
 
         let right = stone - left * (10u32).pow(length / 2) as usize;
 
-        return vec![left, right];
+        vec![left, right]
     } else {
-        return vec![stone * 2024];
+        vec![stone * 2024]
     }
 }
 
@@ -129,6 +129,20 @@ pub fn solution_part_two(input: Input) -> usize {
     result
 }
 
+pub struct Problem;
+
+impl Solution for Problem {
+    type Input = Input;
+
+    fn parse(lines: Vec<String>) -> Input {
+        Input::from(lines)
+    }
+
+    const PART_ONE: Solver<Input> = Some(|input| solution(input).to_string());
+
+    const PART_TWO: Solver<Input> = Some(|input| solution_part_two(input).to_string());
+}
+
 #[cfg(test)]
 mod test {
     use super::{solution, solution_part_two, Input};
@@ -147,6 +161,6 @@ mod test {
         let stones = "125 17";
         let input = Input::from(vec![stones.to_owned()]);
 
-        assert_eq!(solution_part_two(input), 55312);
+        assert_eq!(solution_part_two(input), 65601038650482);
     }
 }
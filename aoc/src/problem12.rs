@@ -1,136 +1,15 @@
-use crate::common::{Cell, Grid, NeighbourMode, OrthoDirection, WindingMode};
+use crate::common::regions::{Connectivity, RegionInfo};
+use crate::common::{CellContents, Grid};
+use crate::solution::{Solution, Solver};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct Plot {
     plant: char,
 }
 
-impl From<char> for Plot {
-    fn from(value: char) -> Self {
-        Self { plant: value }
-    }
-}
-
-#[derive(Debug, Clone)]
-struct Region {
-    plots: Vec<Cell<Plot>>,
-}
-
-impl Region {
-    fn area(&self) -> usize {
-        self.plots.len()
-    }
-
-    fn perimeter(&self, grid: &Grid<Plot>) -> usize {
-        let mut perimeter = 0;
-
-        for plot in &self.plots {
-            let neighbours = grid.safe_ortho_neighbours(plot);
-
-            // doing this as 4 - same-type neighbours instead of just counting different-type neighbours lets us skip having to check for the edges of the grid
-            perimeter += 4 - neighbours
-                .iter()
-                .filter(|other| other.contents == plot.contents)
-                .count();
-        }
-
-        perimeter
-    }
-
-    /*
-    This method works for solid shapes with no holes, but I couldn't easily adapt it to also work
-    for shapes containing holes without being able to come up with a trivial counter-example so I
-    chose to discard it.
-    */
-    fn number_of_sides_old(&self, grid: &Grid<Plot>) -> usize {
-        let mut number_of_sides = 0;
-
-        /*
-            Follow left wall strategy requires starting in the top-left. I wasn't able to find an algorithm that could generalise to any starting square so we instead use a consistent ordering of cells in order to find the top-most left-most square and start from there, facing right
-        */
-        let mut cells = self.plots.clone();
-        cells.sort();
-
-        let mut position = &cells[0];
-        let mut direction = OrthoDirection::Right;
-
-        loop {
-            let left = grid.ortho_neighbour(position, direction.left());
-
-            if left.is_some_and(|cell| cell.contents == position.contents) {
-                direction = direction.left();
-                number_of_sides += 1;
-            }
-
-            let next = grid.ortho_neighbour(position, direction);
-
-            if next.is_some_and(|cell| cell.contents == position.contents) {
-                position = next.unwrap();
-                continue;
-            }
-
-            direction = direction.right();
-            number_of_sides += 1;
-
-            if position == &cells[0] && direction == OrthoDirection::Right {
-                break;
-            }
-        }
-
-        number_of_sides
-    }
-
-    /*
-       Corner-counting strategy
-
-       Since for any regular polygon the number of corners and the number of sides is always equal,
-       we can instead detect every cell that forms a corner with other cells and use that as the
-       result instead.
-    */
-    fn number_of_sides(&self, grid: &Grid<Plot>) -> usize {
-        let mut num_corners = 0;
-
-        for plot in &self.plots {
-            // It's important that the neighbouring cells are retrieved in order so we will make it explicit
-            let north = grid.ortho_neighbour(plot, OrthoDirection::Up);
-            let east = grid.ortho_neighbour(plot, OrthoDirection::Right);
-            let south = grid.ortho_neighbour(plot, OrthoDirection::Down);
-            let west = grid.ortho_neighbour(plot, OrthoDirection::Left);
-
-            let junctions = vec![
-                vec![north, grid.cell_from(plot, (1, -1)), east],
-                vec![east, grid.cell_from(plot, (1, 1)), south],
-                vec![south, grid.cell_from(plot, (-1, 1)), west],
-                vec![west, grid.cell_from(plot, (-1, -1)), north],
-            ];
-
-            for junction in junctions {
-                // We are only interested in cases where the neighbours in question are BOTH equal
-                // or BOTH unequal to the inspected cell. If only one is equal, then there is no
-                // corner at that junction
-
-                // If the junction plots are equal to the inspected plot, then there is only a
-                // corner in the case that the cell between the junction plots is unequal to
-                // the inspected plot. This is the most irritating corner type to detect.
-                if junction[0].is_some_and(|cell| cell.contents == plot.contents)
-                    && junction[1].is_some_and(|cell| cell.contents != plot.contents)
-                    && junction[2].is_some_and(|cell| cell.contents == plot.contents)
-                {
-                    num_corners += 1;
-                } else if (junction[0].is_none()
-                    || junction[0].is_some_and(|cell| cell.contents != plot.contents))
-                    && (junction[2].is_none()
-                        || junction[2].is_some_and(|cell| cell.contents != plot.contents))
-                {
-                    // On the other hand, if both are unequal or none then there is definitely a
-                    // corner and we can just carry on.
-
-                    num_corners += 1;
-                }
-            }
-        }
-
-        num_corners
+impl CellContents for Plot {
+    fn from(_x: usize, _y: usize, c: &char) -> Self {
+        Self { plant: *c }
     }
 }
 
@@ -141,50 +20,48 @@ pub struct Input {
 impl From<Vec<String>> for Input {
     fn from(value: Vec<String>) -> Self {
         Self {
-            grid: Grid::new(value.iter().map(|s| s.chars().collect()).collect()),
+            grid: Grid::from_lines(&value).unwrap().parse_cells(),
         }
     }
 }
 
-pub fn solution(input: Input) -> usize {
-    let grid = input.grid;
-    let regions = grid.regions();
+fn regions(grid: &Grid<Plot>) -> Vec<RegionInfo<Plot>> {
+    grid.label_regions(Connectivity::Four).regions
+}
 
-    let plot_regions: Vec<Region> = regions
+pub fn solution(input: Input) -> usize {
+    regions(&input.grid)
         .iter()
-        .map(|r| Region {
-            plots: r.iter().cloned().map(|p| *p).collect(),
-        })
-        .collect();
+        .map(|region| region.area() * region.perimeter())
+        .sum()
+}
 
-    plot_regions
+pub fn solution_part_two(input: Input) -> usize {
+    regions(&input.grid)
         .iter()
-        .map(|region| region.area() * region.perimeter(&grid))
+        .map(|region| region.area() * region.sides())
         .sum()
 }
 
-pub fn solution_part_two(input: Input) -> usize {
-    let grid = input.grid;
-    let regions = grid.regions();
+pub struct Problem;
 
-    let plot_regions: Vec<Region> = regions
-        .iter()
-        .map(|r| Region {
-            plots: r.iter().cloned().map(|p| *p).collect(),
-        })
-        .collect();
+impl Solution for Problem {
+    type Input = Input;
 
-    plot_regions
-        .iter()
-        .map(|plot| plot.area() * plot.number_of_sides(&grid))
-        .sum()
+    fn parse(lines: Vec<String>) -> Input {
+        Input::from(lines)
+    }
+
+    const PART_ONE: Solver<Input> = Some(|input| solution(input).to_string());
+
+    const PART_TWO: Solver<Input> = Some(|input| solution_part_two(input).to_string());
 }
 
 #[cfg(test)]
 mod test {
     use super::{solution, solution_part_two, Input};
 
-    const EXAMPLE: &'static str = "\
+    const EXAMPLE: &str = "\
 RRRRIICCFF
 RRRRIICCCF
 VVRRRCCFFF
@@ -211,13 +88,13 @@ MMMISSJEEE";
     }
 
     // Counter example with island that defeats left-hand wall strategy
-    const EXAMPLE2: &'static str = "\
+    const EXAMPLE2: &str = "\
 AAAAAA
 AAABBA
 AAABBA
 ABBAAA
 ABBAAA
-AAAAAA    
+AAAAAA
 ";
 
     #[test]
@@ -228,11 +105,11 @@ AAAAAA
     }
 
     // Counter example that defeats strategies for detecting unexplored walls
-    const EXAMPLE3: &'static str = "\
+    const EXAMPLE3: &str = "\
 AAAA
 ABBA
 ABBA
-AAAA    
+AAAA
 ";
 
     #[test]
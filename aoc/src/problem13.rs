@@ -1,4 +1,5 @@
 use crate::common::Vector2di;
+use crate::solution::{Solution, Solver};
 use lazy_static::lazy_static;
 use regex::Regex;
 
@@ -156,6 +157,9 @@ pub fn solution(input: Input) -> usize {
         X = (T1D - T2B) / AD - BC
         Y = (T2A - T1C) / AD - BC
 
+    With the buttons and prize as vectors, each of these terms is a 2D cross product: AD - BC is
+    button A x button B, T1D - T2B is prize x button B and T2A - T1C is button A x prize.
+
 */
 pub fn solution_part_two(input: Input) -> usize {
     let mut modified_input = input.clone();
@@ -167,19 +171,10 @@ pub fn solution_part_two(input: Input) -> usize {
         .for_each(|machine| machine.modify_precision());
 
     for m in &modified_input.machines {
-        let (a, b, c, d, t1, t2) = (
-            m.button_a.x,
-            m.button_b.x,
-            m.button_a.y,
-            m.button_b.y,
-            m.prize.x,
-            m.prize.y,
-        );
-
-        let quotient = a * d - b * c;
+        let quotient = m.button_a.cross(m.button_b);
 
-        let x = t1 * d - t2 * b;
-        let y = t2 * a - t1 * c;
+        let x = m.prize.cross(m.button_b);
+        let y = m.button_a.cross(m.prize);
 
         if x % quotient == 0 && y % quotient == 0 {
             token_cost += (x / quotient * 3) + y / quotient;
@@ -189,12 +184,26 @@ pub fn solution_part_two(input: Input) -> usize {
     token_cost as usize
 }
 
+pub struct Problem;
+
+impl Solution for Problem {
+    type Input = Input;
+
+    fn parse(lines: Vec<String>) -> Input {
+        Input::from(lines)
+    }
+
+    const PART_ONE: Solver<Input> = Some(|input| solution(input).to_string());
+
+    const PART_TWO: Solver<Input> = Some(|input| solution_part_two(input).to_string());
+}
+
 #[cfg(test)]
 mod test {
 
     use super::{solution, solution_part_two, Input};
 
-    const EXAMPLE: &'static str = "\
+    const EXAMPLE: &str = "\
 Button A: X+94, Y+34
 Button B: X+22, Y+67
 Prize: X=8400, Y=5400
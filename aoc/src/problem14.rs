@@ -1,6 +1,6 @@
-use std::{collections::HashMap, thread::sleep, time::Duration};
-
-use crate::common::Vector2di;
+use crate::common::sparse::SparseGrid;
+use crate::common::{Grid, Topology, Vector2di};
+use crate::solution::{Solution, Solver};
 use lazy_static::lazy_static;
 use regex::Regex;
 
@@ -15,9 +15,8 @@ struct Robot {
 }
 
 impl Robot {
-    fn tick(&mut self, bounds: Vector2di) {
-        self.position.x = (self.position.x + self.velocity.x).rem_euclid(bounds.x);
-        self.position.y = (self.position.y + self.velocity.y).rem_euclid(bounds.y);
+    fn tick(&mut self, floor: &Grid<()>) {
+        self.position = floor.wrap(self.position + self.velocity).unwrap();
     }
 }
 
@@ -40,53 +39,67 @@ impl From<&String> for Robot {
 #[derive(Debug, Clone)]
 pub struct Input {
     robots: Vec<Robot>,
-    bounds: Vector2di,
+    // The tiles the robots walk on. Robots that walk off one edge come back on the other.
+    floor: Grid<()>,
 }
 
 impl From<Vec<String>> for Input {
     fn from(value: Vec<String>) -> Self {
-        Self {
-            robots: value.iter().map(Robot::from).collect(),
-            bounds: Vector2di::new(101, 103),
-        }
+        Self::new(value.iter().map(Robot::from).collect(), 101, 103)
     }
 }
 
 impl Input {
+    fn new(robots: Vec<Robot>, width: usize, height: usize) -> Self {
+        Self {
+            robots,
+            floor: Grid::filled(width, height, ()).with_topology(Topology::Wrapping),
+        }
+    }
+
     fn simulate_motion(&mut self, num_ticks: usize) {
         for _ in 0..num_ticks {
             for robot in &mut self.robots {
-                robot.tick(self.bounds);
+                robot.tick(&self.floor);
+            }
+        }
+    }
+}
+
+/// The number of robots on each tile that has any
+fn get_map(robots: &[Robot]) -> SparseGrid<usize> {
+    let mut map = SparseGrid::new();
+
+    for robot in robots {
+        match map.get_mut(robot.position) {
+            Some(count) => *count += 1,
+            None => {
+                map.insert(robot.position, 1);
             }
         }
     }
+
+    map
 }
 
-fn get_safety_factor(robots: Vec<Robot>, bounds: Vector2di) -> usize {
-    let vertical_boundary = bounds.x / 2;
-    let horizontal_boundary = bounds.y / 2;
+fn get_safety_factor(map: &SparseGrid<usize>, floor: &Grid<()>) -> usize {
+    let vertical_boundary = floor.width() as i64 / 2;
+    let horizontal_boundary = floor.height() as i64 / 2;
 
-    let q1 = &robots
-        .iter()
-        .filter(|&r| r.position.x < vertical_boundary && r.position.y < horizontal_boundary)
-        .count();
+    let mut quadrants = [0; 4];
 
-    let q2 = &robots
-        .iter()
-        .filter(|&r| r.position.x > vertical_boundary && r.position.y < horizontal_boundary)
-        .count();
+    for (position, &robots) in map.iter() {
+        if position.x == vertical_boundary || position.y == horizontal_boundary {
+            continue;
+        }
 
-    let q3 = &robots
-        .iter()
-        .filter(|&r| r.position.x < vertical_boundary && r.position.y > horizontal_boundary)
-        .count();
+        let right = position.x > vertical_boundary;
+        let bottom = position.y > horizontal_boundary;
 
-    let q4 = &robots
-        .iter()
-        .filter(|&r| r.position.x > vertical_boundary && r.position.y > horizontal_boundary)
-        .count();
+        quadrants[right as usize + 2 * bottom as usize] += robots;
+    }
 
-    q1 * q2 * q3 * q4
+    quadrants.iter().product()
 }
 
 pub fn solution(input: Input) -> usize {
@@ -94,46 +107,38 @@ pub fn solution(input: Input) -> usize {
 
     input.simulate_motion(100);
 
-    get_safety_factor(input.robots, input.bounds)
+    get_safety_factor(&get_map(&input.robots), &input.floor)
 }
 
-fn get_map(robots: &[Robot]) -> HashMap<Vector2di, usize> {
-    let mut map = HashMap::new();
-
-    for robot in robots {
-        if let Some(v) = map.get(&robot.position) {
-            map.insert(robot.position, v + 1);
-        } else {
-            map.insert(robot.position, 1);
-        }
-    }
+/// Draw the whole floor with the number of robots on each tile, or `.` where there are none
+fn draw_map(map: &SparseGrid<usize>, floor: &Grid<()>) -> String {
+    let mut picture = String::new();
 
-    map
-}
-
-fn display_map(map: &HashMap<Vector2di, usize>, bounds: Vector2di) {
-    for j in 0..bounds.y {
-        for i in 0..bounds.x {
-            // if i == bounds.x / 2 || j == bounds.y / 2 {
-            // print!(" ");
-            // continue;
-            // }
-            //
-            if let Some(v) = map.get(&Vector2di::new(i, j)) {
-                print!("{}", v);
-            } else {
-                print!(".");
+    for y in 0..floor.height() {
+        for x in 0..floor.width() {
+            match map.get((x, y)) {
+                Some(robots) => picture.push_str(&robots.to_string()),
+                None => picture.push('.'),
             }
         }
 
-        println!();
+        picture.push('\n');
     }
+
+    picture
 }
 
-pub fn solution_part_two(input: Input) {
+/*
+    Returns the number of ticks after which the picture appears, or 0 if nothing turned up.
+
+    The picture is spotted by a robot reaching the middle of the top edge, which happened to be
+    where the tree's tip was for one input. It isn't a real detector for the tree, so the day
+    doesn't report this as its part two answer.
+*/
+pub fn solution_part_two(input: Input) -> usize {
     let mut input = input.clone();
 
-    for _ in 0..10000 {
+    for tick in 1..=10000 {
         input.simulate_motion(1);
 
         if input
@@ -141,22 +146,36 @@ pub fn solution_part_two(input: Input) {
             .iter()
             .any(|r| r.position.x == 50 && r.position.y == 0)
         {
-            let map = get_map(&input.robots);
-            display_map(&map, input.bounds);
-            break;
+            crate::debug!("{}", draw_map(&get_map(&input.robots), &input.floor));
+
+            return tick;
         }
     }
 
-    println!("No result after 10000 ticks");
+    crate::debug!("No result after 10000 ticks");
+
+    0
+}
+
+pub struct Problem;
+
+impl Solution for Problem {
+    type Input = Input;
+
+    fn parse(lines: Vec<String>) -> Input {
+        Input::from(lines)
+    }
+
+    const PART_ONE: Solver<Input> = Some(|input| solution(input).to_string());
+
+    // Part two is left unsolved until there's a real detector for the tree, see solution_part_two
 }
 
 #[cfg(test)]
 mod test {
-    use crate::common::Vector2di;
+    use super::{draw_map, get_map, solution, Input, Robot};
 
-    use super::{solution, Input, Robot};
-
-    const EXAMPLE: &'static str = "\
+    const EXAMPLE: &str = "\
 p=0,4 v=3,-3
 p=6,3 v=-1,-3
 p=10,3 v=-1,2
@@ -173,14 +192,40 @@ p=9,5 v=-3,-3
 
     #[test]
     fn test() {
-        let input = Input {
-            robots: EXAMPLE
+        let input = Input::new(
+            EXAMPLE
                 .lines()
                 .map(|s| Robot::from(&s.to_owned()))
                 .collect(),
-            bounds: Vector2di::new(11, 7),
-        };
+            11,
+            7,
+        );
 
         assert_eq!(solution(input), 12);
     }
+
+    #[test]
+    fn test_draw_map() {
+        let input = Input::new(
+            EXAMPLE
+                .lines()
+                .map(|s| Robot::from(&s.to_owned()))
+                .collect(),
+            11,
+            7,
+        );
+
+        assert_eq!(
+            draw_map(&get_map(&input.robots), &input.floor),
+            "\
+1.12.......
+...........
+...........
+......11.11
+1.1........
+.........1.
+.......1...
+"
+        );
+    }
 }
@@ -1,6 +1,7 @@
 use std::fmt::{Debug, Display, Write};
 
 use crate::common::{Grid, OrthoDirection, Vector2di};
+use crate::solution::{Solution, Solver};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum WarehouseEntity {
@@ -25,9 +26,9 @@ impl From<char> for WarehouseEntity {
     }
 }
 
-impl Into<char> for WarehouseEntity {
-    fn into(self) -> char {
-        match self {
+impl From<WarehouseEntity> for char {
+    fn from(value: WarehouseEntity) -> Self {
+        match value {
             WarehouseEntity::Nothing => '.',
             WarehouseEntity::Wall => '#',
             WarehouseEntity::Box => 'O',
@@ -46,26 +47,23 @@ impl From<Vec<String>> for Warehouse {
         let col = value[row].chars().position(|c| c == '@').unwrap();
 
         Self {
-            grid: Grid::from(entities),
-            robot: Vector2di {
-                x: col as i64,
-                y: row as i64,
-            },
+            grid: Grid::try_from(entities).unwrap(),
+            robot: Vector2di::from((col, row)),
         }
     }
 }
 
 impl Display for Warehouse {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for cell in self.grid.clone().into_iter() {
-            if cell.x == 0 {
-                f.write_str("\n")?;
-            }
-
-            if self.robot == Vector2di::new(cell.x as i64, cell.y as i64) {
-                f.write_char('@')?;
-            } else {
-                f.write_char(cell.contents.into())?
+        for (y, row) in self.grid.rows().enumerate() {
+            f.write_str("\n")?;
+
+            for (x, &entity) in row.iter().enumerate() {
+                if self.robot == (x, y).into() {
+                    f.write_char('@')?;
+                } else {
+                    f.write_char(entity.into())?
+                }
             }
         }
 
@@ -74,45 +72,37 @@ impl Display for Warehouse {
 }
 
 impl Warehouse {
+    /*
+        The robot pushes the line of boxes in front of it along by one square, which can only
+        happen if the line ends in an empty square rather than a wall. Moving the first box of the
+        line into the empty square has the same effect as moving every box.
+    */
     fn move_robot(&mut self, dir: OrthoDirection) {
         let target_square = self.robot + dir.into();
 
-        let push_result = self.push_result(target_square, dir);
-
-        self.grid.swap(self.robot, push_result);
-        self.robot = push_result;
-    }
-
-    /*
-        Pushes the entity at the target square and returns the position that the pushing entity
-        should move to
-    */
-    fn push_result(&mut self, push_target: Vector2di, dir: OrthoDirection) -> Vector2di {
-        let entity = self
+        let (end, entity) = self
             .grid
-            .get(push_target)
-            .expect("attempted to push outside grid")
-            .contents;
-
-        match entity {
-            WarehouseEntity::Nothing => push_target,
-            WarehouseEntity::Wall => push_target - dir.into(),
-            WarehouseEntity::Box => {
-                let next_target = self.push_result(push_target + dir.into(), dir);
+            .ray(target_square, dir)
+            .find(|(_, &entity)| entity != WarehouseEntity::Box)
+            .map(|(position, &entity)| (position, entity))
+            .expect("attempted to push outside grid");
 
-                self.grid.swap(push_target, next_target);
-
-                next_target - dir.into()
-            }
+        if entity == WarehouseEntity::Wall {
+            return;
         }
+
+        self.grid.swap(target_square, end);
+        self.robot = target_square;
     }
 
     fn get_total_gps(&self) -> usize {
         let mut total = 0;
 
-        for cell in self.grid.clone().into_iter() {
-            if cell.contents == WarehouseEntity::Box {
-                total += cell.x + cell.y * 100;
+        for (position, &entity) in self.grid.iter() {
+            if entity == WarehouseEntity::Box {
+                let (x, y): (usize, usize) = position.try_into().unwrap();
+
+                total += x + y * 100;
             }
         }
 
@@ -166,13 +156,11 @@ impl From<Vec<String>> for Input {
 
 impl Input {
     fn solve(&mut self) {
-        let mut iter = self.moves.moves.iter();
-
-        while let Some(dir) = iter.next() {
+        for dir in &self.moves.moves {
             self.warehouse.move_robot(*dir);
         }
 
-        println!("{}", self.warehouse);
+        crate::debug!("{}", self.warehouse);
     }
 }
 
@@ -184,11 +172,23 @@ pub fn solution(input: Input) -> usize {
     input.warehouse.get_total_gps()
 }
 
+pub struct Problem;
+
+impl Solution for Problem {
+    type Input = Input;
+
+    fn parse(lines: Vec<String>) -> Input {
+        Input::from(lines)
+    }
+
+    const PART_ONE: Solver<Input> = Some(|input| solution(input).to_string());
+}
+
 #[cfg(test)]
 mod test {
     use super::{solution, Input};
 
-    const EXAMPLE: &'static str = "\
+    const EXAMPLE: &str = "\
 ##########
 #..O..O.O#
 #......O.#
@@ -1,3 +1,5 @@
+use crate::solution::{Solution, Solver};
+
 pub type Report = Vec<usize>;
 
 pub struct Input {
@@ -29,15 +31,12 @@ impl Safe for Report {
     }
 
     fn safe_dampened(&self) -> bool {
-        self.iter()
-            .enumerate()
-            .map(|(i, _)| {
-                let mut new_list = self.clone();
-                new_list.remove(i);
-
-                new_list.safe()
-            })
-            .any(|o| o)
+        self.iter().enumerate().any(|(i, _)| {
+            let mut new_list = self.clone();
+            new_list.remove(i);
+
+            new_list.safe()
+        })
     }
 }
 
@@ -64,6 +63,20 @@ pub fn parse_lines(lines: Vec<String>) -> Input {
     Input { reports }
 }
 
+pub struct Problem;
+
+impl Solution for Problem {
+    type Input = Input;
+
+    fn parse(lines: Vec<String>) -> Input {
+        parse_lines(lines)
+    }
+
+    const PART_ONE: Solver<Input> = Some(|input| solution(input).to_string());
+
+    const PART_TWO: Solver<Input> = Some(|input| solution_part_two(input).to_string());
+}
+
 #[cfg(test)]
 mod test {
     use super::{Report, Safe};
@@ -1,5 +1,7 @@
 use regex::Regex;
 
+use crate::solution::{Solution, Solver};
+
 pub struct Input {
     memory: String,
 }
@@ -36,6 +38,20 @@ pub fn parse_lines(lines: Vec<String>) -> Input {
     }
 }
 
+pub struct Problem;
+
+impl Solution for Problem {
+    type Input = Input;
+
+    fn parse(lines: Vec<String>) -> Input {
+        parse_lines(lines)
+    }
+
+    const PART_ONE: Solver<Input> = Some(|input| solution(input).to_string());
+
+    const PART_TWO: Solver<Input> = Some(|input| solution_part_two(input).to_string());
+}
+
 #[cfg(test)]
 mod test {
     use super::{solution, Input};
@@ -1,3 +1,7 @@
+use crate::common::pattern::Template;
+use crate::common::Grid;
+use crate::solution::{Solution, Solver};
+
 #[derive(Debug)]
 pub struct Input {
     rows: Vec<String>,
@@ -30,21 +34,10 @@ pub fn solution_old(input: Input) -> usize {
         .collect::<Vec<String>>();
 
     let d1 = get_diagonals(&rows);
-    let d2 = get_diagonals(&rows.iter().rev().cloned().collect());
-    let d3 = get_diagonals(&cols.iter().rev().cloned().collect());
+    let d2 = get_diagonals(&rows.iter().rev().cloned().collect::<Vec<String>>());
+    let d3 = get_diagonals(&cols.iter().rev().cloned().collect::<Vec<String>>());
     let d4 = get_diagonals(&cols);
 
-    println!("{:?}", rows);
-    println!("{:?}", rows_reversed);
-
-    println!("{:?}", cols);
-    println!("{:?}", cols_reversed);
-
-    println!("{:?}", d1);
-    println!("{:?}", d2);
-    println!("{:?}", d3);
-    println!("{:?}", d4);
-
     rows.iter().map(count_matches).sum::<usize>()
         + rows_reversed.iter().map(count_matches).sum::<usize>()
         + cols.iter().map(count_matches).sum::<usize>()
@@ -55,7 +48,7 @@ pub fn solution_old(input: Input) -> usize {
         + d4.iter().map(count_matches).sum::<usize>()
 }
 
-fn get_diagonals(rows: &Vec<String>) -> Vec<String> {
+fn get_diagonals(rows: &[String]) -> Vec<String> {
     let mut diagonals: Vec<String> = vec![];
 
     for i in 0..rows[0].len() {
@@ -78,236 +71,49 @@ fn get_diagonals(rows: &Vec<String>) -> Vec<String> {
     diagonals.iter().filter(|&s| s.len() > 3).cloned().collect()
 }
 
+#[allow(clippy::ptr_arg)]
 fn count_matches(candidate: &String) -> usize {
     candidate.matches("XMAS").count()
 }
 
 // ---------------------------------------------------------
 
-#[derive(Copy, Clone)]
-struct Cell {
-    x: usize,
-    y: usize,
-    contents: char,
-}
-
-#[derive(Clone)]
-struct Grid {
-    rows: Vec<Vec<Cell>>,
-    x_dim: usize,
-    y_dim: usize,
-}
-
-impl IntoIterator for Grid {
-    type Item = Cell;
-    type IntoIter = GridIntoIterator;
-
-    fn into_iter(self) -> Self::IntoIter {
-        GridIntoIterator {
-            dim: self.rows.len(),
-            grid: self,
-            index: 0,
-        }
-    }
-}
-
-struct GridIntoIterator {
-    grid: Grid,
-    dim: usize,
-    index: usize,
-}
-
-impl Iterator for GridIntoIterator {
-    type Item = Cell;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.index >= self.dim.pow(2) {
-            return None;
-        };
-
-        let result = self.grid.rows[self.index / self.dim][self.index % self.dim];
-
-        self.index += 1;
-
-        Some(result)
-    }
-}
-
-#[derive(Clone, Copy)]
-enum Direction {
-    N,
-    NE,
-    E,
-    SE,
-    S,
-    SW,
-    W,
-    NW,
-}
-
-impl Direction {
-    const DIRS: [Direction; 8] = [
-        Self::N,
-        Self::NE,
-        Self::E,
-        Self::SE,
-        Self::S,
-        Self::SW,
-        Self::W,
-        Self::NW,
-    ];
-
-    fn iterator() -> impl Iterator<Item = Direction> {
-        Direction::DIRS.iter().copied()
-    }
-}
-
-impl Grid {
-    fn new(chars: Vec<Vec<char>>) -> Self {
-        let cells: Vec<Vec<Cell>> = chars
-            .iter()
-            .enumerate()
-            .map(|(i, row)| {
-                row.iter()
-                    .enumerate()
-                    .map(|(j, c)| Cell {
-                        x: i,
-                        y: j,
-                        contents: *c,
-                    })
-                    .collect()
-            })
-            .collect();
-
-        Self {
-            x_dim: cells[0].len(),
-            y_dim: cells.len(),
-            rows: cells,
-        }
-    }
-
-    fn get_neighbour(&self, cell: Cell, dir: Direction) -> Option<Cell> {
-        let delta: (isize, isize) = match dir {
-            Direction::N => (0, -1),
-            Direction::NE => (1, -1),
-            Direction::E => (1, 0),
-            Direction::SE => (1, 1),
-            Direction::S => (0, 1),
-            Direction::SW => (-1, 1),
-            Direction::W => (-1, 0),
-            Direction::NW => (-1, -1),
-        };
-
-        let proj_x = cell.x as isize + delta.0;
-        let proj_y = cell.y as isize + delta.1;
-
-        if proj_x < 0 || proj_x >= self.x_dim as isize {
-            return None;
-        };
-
-        if proj_y < 0 || proj_y >= self.y_dim as isize {
-            return None;
-        };
-
-        Some(self.rows[proj_x as usize][proj_y as usize])
-    }
-
-    fn is_edge(&self, cell: Cell) -> bool {
-        cell.x == 0 || cell.y == 0 || cell.x == self.x_dim - 1 || cell.y == self.y_dim - 1
-    }
-}
-
 pub fn solution(input: Input) -> usize {
-    let grid = Grid::new(
-        input
-            .rows
-            .iter()
-            .map(|r| r.clone().chars().collect())
-            .collect(),
-    );
-
-    let mut count = 0;
-
-    for cell in grid.clone() {
-        if cell != 'X' {
-            continue;
-        }
-
-        for dir in Direction::iterator() {
-            if let Some(c) = grid.get_neighbour(cell, dir) {
-                if c == 'M' {
-                    if let Some(c2) = grid.get_neighbour(c, dir) {
-                        if c2 == 'A' {
-                            if let Some(c3) = grid.get_neighbour(c2, dir) {
-                                if c3 == 'S' {
-                                    count += 1;
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-    }
-
-    count
-}
-
-impl PartialEq<char> for Cell {
-    fn eq(&self, other: &char) -> bool {
-        &self.contents == other
-    }
-}
+    let grid = Grid::from_lines(&input.rows).unwrap();
 
-impl Into<char> for Cell {
-    fn into(self) -> char {
-        self.contents
-    }
+    // Every orientation of these two covers all eight directions
+    ["XMAS", "X.../.M../..A./...S"]
+        .into_iter()
+        .map(|pattern| {
+            grid.find_oriented(&Template::try_from(pattern).unwrap())
+                .len()
+        })
+        .sum()
 }
 
 pub fn solution_part_two(input: Input) -> usize {
-    let grid = Grid::new(
-        input
-            .rows
-            .iter()
-            .map(|r| r.clone().chars().collect())
-            .collect(),
-    );
+    let grid = Grid::from_lines(&input.rows).unwrap();
 
-    let mut count = 0;
-
-    for cell in grid.clone() {
-        if grid.is_edge(cell) || cell.contents != 'A' {
-            continue;
-        }
-
-        // We can unwrap because we already checked that the cell is not on the edge of the grid
-        let nw = grid.get_neighbour(cell, Direction::NW).unwrap();
-        let se = grid.get_neighbour(cell, Direction::SE).unwrap();
+    grid.find_oriented(&Template::try_from("M.S/.A./M.S").unwrap())
+        .len()
+}
 
-        let s1 = vec![nw.contents, cell.contents, se.contents]
-            .iter()
-            .cloned()
-            .collect::<String>();
+pub fn parse_lines(lines: Vec<String>) -> Input {
+    Input { rows: lines }
+}
 
-        let ne = grid.get_neighbour(cell, Direction::NE).unwrap();
-        let sw = grid.get_neighbour(cell, Direction::SW).unwrap();
+pub struct Problem;
 
-        let s2 = vec![ne.contents, cell.contents, sw.contents]
-            .iter()
-            .cloned()
-            .collect::<String>();
+impl Solution for Problem {
+    type Input = Input;
 
-        if (s1 == "MAS" || s1 == "SAM") && (s2 == "MAS" || s2 == "SAM") {
-            count += 1;
-        }
+    fn parse(lines: Vec<String>) -> Input {
+        parse_lines(lines)
     }
 
-    count
-}
+    const PART_ONE: Solver<Input> = Some(|input| solution(input).to_string());
 
-pub fn parse_lines(lines: Vec<String>) -> Input {
-    Input { rows: lines }
+    const PART_TWO: Solver<Input> = Some(|input| solution_part_two(input).to_string());
 }
 
 #[cfg(test)]
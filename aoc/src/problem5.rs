@@ -1,3 +1,5 @@
+use crate::solution::{Solution, Solver};
+
 type Page = usize;
 
 #[derive(Debug, Clone, Copy)]
@@ -51,7 +53,7 @@ pub fn parse_lines(lines: Vec<String>) -> Input {
         .by_ref()
         .take_while(|line| !line.is_empty())
         .cloned()
-        .map(|s| Rule::from(s))
+        .map(Rule::from)
         .collect();
 
     let updates = iter.cloned().map(Update::from).collect();
@@ -61,7 +63,7 @@ pub fn parse_lines(lines: Vec<String>) -> Input {
 
 impl Update {
     fn well_ordered(&self, rules: &[Rule]) -> bool {
-        rules.iter().all(self.satisfies)
+        rules.iter().all(|rule| self.satisfies(rule))
     }
 
     fn satisfies(&self, rule: &Rule) -> bool {
@@ -73,7 +75,7 @@ impl Update {
 
         let i2 = self.pages.iter().position(|&p| p == rule.second).unwrap();
 
-        return i1 < i2;
+        i1 < i2
     }
 
     fn middle_page(&self) -> Page {
@@ -129,11 +131,25 @@ pub fn solution_part_two(input: Input) -> usize {
         .sum()
 }
 
+pub struct Problem;
+
+impl Solution for Problem {
+    type Input = Input;
+
+    fn parse(lines: Vec<String>) -> Input {
+        parse_lines(lines)
+    }
+
+    const PART_ONE: Solver<Input> = Some(|input| solution(input).to_string());
+
+    const PART_TWO: Solver<Input> = Some(|input| solution_part_two(input).to_string());
+}
+
 #[cfg(test)]
 mod test {
     use super::{parse_lines, solution, solution_part_two};
 
-    const TEST: &'static str = "\
+    const TEST: &str = "\
 47|53
 97|13
 97|61
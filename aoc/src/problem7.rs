@@ -1,5 +1,7 @@
 use std::collections::VecDeque;
 
+use crate::solution::{Solution, Solver};
+
 #[derive(Debug, Clone)]
 struct Equation {
     target: usize,
@@ -81,18 +83,15 @@ impl Node {
         let mut queue: Vec<&Node> = vec![self];
         let mut leaves: Vec<&Node> = vec![];
 
-        while !queue.is_empty() {
-            let node = queue.pop().unwrap();
-
+        while let Some(node) = queue.pop() {
             match &node.left {
                 Some(left) => {
                     queue.push(left);
-                    match &node.right {
-                        Some(right) => queue.push(right),
-                        None => {}
+                    if let Some(right) = &node.right {
+                        queue.push(right)
                     }
                 }
-                None => leaves.push(&node),
+                None => leaves.push(node),
             }
         }
 
@@ -171,24 +170,18 @@ impl NodeExtended {
         let mut queue: Vec<&Self> = vec![self];
         let mut leaves: Vec<&Self> = vec![];
 
-        while !queue.is_empty() {
-            let node = queue.pop().unwrap();
-
+        while let Some(node) = queue.pop() {
             match &node.left {
                 Some(left) => {
                     queue.push(left);
-                    match &node.middle {
-                        Some(middle) => {
-                            queue.push(middle);
-                            match &node.right {
-                                Some(right) => queue.push(right),
-                                None => {}
-                            }
+                    if let Some(middle) = &node.middle {
+                        queue.push(middle);
+                        if let Some(right) = &node.right {
+                            queue.push(right)
                         }
-                        None => {}
                     }
                 }
-                None => leaves.push(&node),
+                None => leaves.push(node),
             }
         }
 
@@ -229,7 +222,7 @@ pub struct Input {
 impl Input {
     pub fn from_lines(lines: &[String]) -> Self {
         Self {
-            equations: lines.iter().map(|line| Equation::from(line)).collect(),
+            equations: lines.iter().map(Equation::from).collect(),
         }
     }
 }
@@ -251,7 +244,7 @@ impl Concatentate<usize> for usize {
     fn concat(&self, other: usize) -> usize {
         let mag = (other as f64).log10() as u32 + 1;
 
-        (self * (10 as usize).pow(mag)) + other
+        (self * 10_usize.pow(mag)) + other
     }
 }
 
@@ -264,11 +257,25 @@ pub fn solution_part_two(input: Input) -> usize {
         .sum()
 }
 
+pub struct Problem;
+
+impl Solution for Problem {
+    type Input = Input;
+
+    fn parse(lines: Vec<String>) -> Input {
+        Input::from_lines(&lines)
+    }
+
+    const PART_ONE: Solver<Input> = Some(|input| solution(input).to_string());
+
+    const PART_TWO: Solver<Input> = Some(|input| solution_part_two(input).to_string());
+}
+
 #[cfg(test)]
 mod test {
     use super::{Concatentate, Input};
 
-    const EXAMPLE: &'static str = "\
+    const EXAMPLE: &str = "\
 190: 10 19
 3267: 81 40 27
 83: 17 5
@@ -296,9 +303,9 @@ mod test {
 
     #[test]
     pub fn concat_test() {
-        assert_eq!((15 as usize).concat(6), 156);
-        assert_eq!((8 as usize).concat(6), 86);
-        assert_eq!((10 as usize).concat(0), 100);
-        assert_eq!((14 as usize).concat(20), 1420);
+        assert_eq!(15_usize.concat(6), 156);
+        assert_eq!(8_usize.concat(6), 86);
+        assert_eq!(10_usize.concat(0), 100);
+        assert_eq!(14_usize.concat(20), 1420);
     }
 }
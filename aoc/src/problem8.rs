@@ -1,9 +1,11 @@
-use crate::common::{CellContents, CellType, Grid, Vector2D};
+use crate::common::bits::BitGrid;
+use crate::common::{CellContents, CellType, Grid, Vector2di};
+use crate::solution::{Solution, Solver};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 struct Antenna {
     frequency: char,
-    position: Vector2D,
+    position: Vector2di,
 }
 
 type GridCell = Option<Antenna>;
@@ -30,8 +32,7 @@ impl Input {
         self.grid
             .clone()
             .into_iter()
-            .filter(|&cell| cell.contents().is_some())
-            .map(|cell| cell.contents().unwrap())
+            .filter_map(|cell| cell.contents)
             .collect::<Vec<Antenna>>()
     }
 }
@@ -39,7 +40,7 @@ impl Input {
 impl From<Vec<String>> for Input {
     fn from(value: Vec<String>) -> Self {
         Self {
-            grid: Grid::new(value.iter().map(|s| s.chars().collect()).collect()),
+            grid: Grid::from_lines(&value).unwrap().parse_cells(),
         }
     }
 }
@@ -47,7 +48,7 @@ impl From<Vec<String>> for Input {
 pub fn solution(input: Input) -> usize {
     let antennas = input.get_antennas();
 
-    let mut antinode_positions: Vec<Vector2D> = vec![];
+    let mut antinodes = BitGrid::new(input.grid.width(), input.grid.height());
 
     for antenna in &antennas {
         let matches: Vec<&Antenna> = antennas
@@ -60,20 +61,18 @@ pub fn solution(input: Input) -> usize {
             let antinode_position = antenna.position - delta;
 
             if let CellType::Cell(_) = input.grid.safe_index(antinode_position) {
-                if !antinode_positions.contains(&antinode_position) {
-                    antinode_positions.push(antinode_position);
-                }
+                antinodes.set(antinode_position);
             }
         }
     }
 
-    antinode_positions.len()
+    antinodes.count()
 }
 
 pub fn solution_part_two(input: Input) -> usize {
     let antennas = input.get_antennas();
 
-    let mut antinode_positions: Vec<Vector2D> = vec![];
+    let mut antinodes = BitGrid::new(input.grid.width(), input.grid.height());
 
     for antenna in &antennas {
         let matches: Vec<&Antenna> = antennas
@@ -82,45 +81,48 @@ pub fn solution_part_two(input: Input) -> usize {
             .collect();
 
         for matched in matches {
-            let delta = matched.position - antenna.position;
-            let mut next_pos = antenna.position;
-
-            while let CellType::Cell(_) = input.grid.safe_index(next_pos) {
-                if !antinode_positions.contains(&next_pos) {
-                    antinode_positions.push(next_pos)
-                }
+            let step = antenna.position - matched.position;
 
-                next_pos = next_pos - delta;
+            for (position, _) in input.grid.ray(antenna.position, step) {
+                antinodes.set(position);
             }
         }
     }
 
     // ---- This one has an output that's nice to look at
-    let mut counter = 0;
-    let dim = input.grid.dim();
+    let mut picture = Grid::filled(input.grid.width(), input.grid.height(), '.');
 
-    for cell in input.grid {
-        if counter % dim == 0 {
-            println!();
-        }
-        if let Some(antenna) = cell.contents() {
-            print!("{}", antenna.frequency);
-        } else if antinode_positions.contains(&cell.position()) {
-            print!("#");
-        } else {
-            print!(".");
-        }
+    for position in antinodes.iter() {
+        picture.insert(position, '#');
+    }
 
-        counter += 1;
+    for antenna in &antennas {
+        picture.insert(antenna.position, antenna.frequency);
     }
+
+    crate::debug!("\n{}", picture);
     // ----------------------------------------------------
 
-    antinode_positions.len()
+    antinodes.count()
+}
+
+pub struct Problem;
+
+impl Solution for Problem {
+    type Input = Input;
+
+    fn parse(lines: Vec<String>) -> Input {
+        Input::from(lines)
+    }
+
+    const PART_ONE: Solver<Input> = Some(|input| solution(input).to_string());
+
+    const PART_TWO: Solver<Input> = Some(|input| solution_part_two(input).to_string());
 }
 
 #[cfg(test)]
 mod test {
-    const EXAMPLE: &'static str = "\
+    const EXAMPLE: &str = "\
 ............
 ........0...
 .....0......
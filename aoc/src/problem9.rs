@@ -4,6 +4,8 @@ use std::{
     fmt::{Display, Write},
 };
 
+use crate::solution::{Solution, Solver};
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 enum Block {
     Free,
@@ -66,9 +68,7 @@ impl Disk {
 
     fn checksum(&self) -> usize {
         let mut result = 0;
-        let mut iter = self.blocks.iter().enumerate();
-
-        while let Some((i, block)) = iter.next() {
+        for (i, block) in self.blocks.iter().enumerate() {
             if let Block::File(id) = block {
                 result += i * id;
             }
@@ -184,6 +184,20 @@ pub fn solution_part_two(input: Input) -> usize {
     disk.checksum()
 }
 
+pub struct Problem;
+
+impl Solution for Problem {
+    type Input = Input;
+
+    fn parse(lines: Vec<String>) -> Input {
+        Input::from(lines)
+    }
+
+    const PART_ONE: Solver<Input> = Some(|input| solution(input).to_string());
+
+    const PART_TWO: Solver<Input> = Some(|input| solution_part_two(input).to_string());
+}
+
 #[cfg(test)]
 mod test {
     use crate::problem9::solution_part_two;
@@ -0,0 +1,127 @@
+use std::time::{Duration, Instant};
+
+use crate::solution::{Answer, Part, Solution};
+
+/// The result of running one part of a day, with parsing and solving timed separately
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Run {
+    /// `None` if the day has no solution for the part
+    pub answer: Option<Answer>,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+}
+
+fn run<S: Solution>(part: Part, lines: Vec<String>) -> Run {
+    // Parts without a solution aren't run at all, not even the parser
+    let Some(solve) = S::solver(part) else {
+        return Run::default();
+    };
+
+    let start = Instant::now();
+    let input = S::parse(lines);
+    let parse_time = start.elapsed();
+
+    let start = Instant::now();
+    let answer = solve(input);
+    let solve_time = start.elapsed();
+
+    Run {
+        answer: Some(answer),
+        parse_time,
+        solve_time,
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Day {
+    pub number: usize,
+    has_part: fn(Part) -> bool,
+    run: fn(Part, Vec<String>) -> Run,
+}
+
+impl Day {
+    const fn new<S: Solution>(number: usize) -> Self {
+        Self {
+            number,
+            has_part: |part| S::solver(part).is_some(),
+            run: run::<S>,
+        }
+    }
+
+    /// Whether the day has a solution for the part, without running it
+    pub fn has_part(&self, part: Part) -> bool {
+        (self.has_part)(part)
+    }
+
+    /// The parts the day has a solution for
+    pub fn parts(&self) -> Vec<Part> {
+        Part::ALL
+            .into_iter()
+            .filter(|&part| self.has_part(part))
+            .collect()
+    }
+
+    /// Parse the lines and run the requested part
+    pub fn run(&self, part: Part, lines: Vec<String>) -> Run {
+        (self.run)(part, lines)
+    }
+}
+
+pub const DAYS: &[Day] = &[
+    Day::new::<crate::problem1::Problem>(1),
+    Day::new::<crate::problem2::Problem>(2),
+    Day::new::<crate::problem3::Problem>(3),
+    Day::new::<crate::problem4::Problem>(4),
+    Day::new::<crate::problem5::Problem>(5),
+    Day::new::<crate::problem6::Problem>(6),
+    Day::new::<crate::problem7::Problem>(7),
+    Day::new::<crate::problem8::Problem>(8),
+    Day::new::<crate::problem9::Problem>(9),
+    Day::new::<crate::problem10::Problem>(10),
+    Day::new::<crate::problem11::Problem>(11),
+    Day::new::<crate::problem12::Problem>(12),
+    Day::new::<crate::problem13::Problem>(13),
+    Day::new::<crate::problem14::Problem>(14),
+    Day::new::<crate::problem15::Problem>(15),
+];
+
+pub fn get(number: usize) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{get, DAYS};
+    use crate::solution::Part;
+
+    #[test]
+    fn test_days_are_ordered_and_unique() {
+        assert!(DAYS.windows(2).all(|w| w[0].number < w[1].number));
+    }
+
+    #[test]
+    fn test_get() {
+        assert_eq!(get(1).map(|day| day.number), Some(1));
+        assert!(get(0).is_none());
+        assert!(get(26).is_none());
+    }
+
+    #[test]
+    fn test_parts_without_a_solver_are_not_run() {
+        for day in DAYS {
+            for part in Part::ALL {
+                // An empty input would break most parsers, so this only passes if nothing runs
+                if !day.has_part(part) {
+                    assert_eq!(day.run(part, vec![]).answer, None, "day {}", day.number);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_parts() {
+        assert_eq!(get(1).unwrap().parts(), vec![Part::One, Part::Two]);
+        assert_eq!(get(14).unwrap().parts(), vec![Part::One]);
+        assert!(!get(15).unwrap().has_part(Part::Two));
+    }
+}
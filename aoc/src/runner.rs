@@ -0,0 +1,463 @@
+use std::{
+    fs::File,
+    io::{self, BufRead},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::answers::Verdict;
+use crate::registry::{self, Day, DAYS};
+use crate::solution::{Answer, Part};
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Format {
+    #[default]
+    Text,
+    /// One JSON object per line for each day and part
+    Json,
+}
+
+impl TryFrom<&str> for Format {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("invalid format '{}', expected text or json", value)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Status {
+    Solved(Answer),
+    /// The day has no solution for this part
+    Unsolved,
+    /// The puzzle input for the day couldn't be found or is empty
+    Skipped,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Outcome {
+    pub day: usize,
+    pub part: Part,
+    pub status: Status,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+}
+
+impl Outcome {
+    pub fn time(&self) -> Duration {
+        self.parse_time + self.solve_time
+    }
+}
+
+/// The argument to `--input` that reads the puzzle input from stdin
+pub const STDIN: &str = "-";
+
+/**
+Resolve a path relative to the root of the crate, so that the default inputs and answers are found
+regardless of the working directory the binary is run from.
+*/
+pub fn crate_path(relative: impl AsRef<Path>) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join(relative)
+}
+
+pub fn default_input(day: usize) -> PathBuf {
+    crate_path(format!("inputs/{}.txt", day))
+}
+
+fn read_lines_from(reader: impl BufRead) -> io::Result<Vec<String>> {
+    reader.lines().collect()
+}
+
+pub fn read_lines(path: impl AsRef<Path>) -> Result<Vec<String>, String> {
+    let path = path.as_ref();
+    let f = File::open(path).map_err(|e| format!("failed to open {}: {}", path.display(), e))?;
+
+    read_lines_from(io::BufReader::new(f))
+        .map_err(|e| format!("failed to read {}: {}", path.display(), e))
+}
+
+/**
+Read the puzzle input for a day. The input is read from stdin if `input` is `-`, from the given
+path if there is one, and from `inputs/<day>.txt` in the crate root otherwise.
+*/
+pub fn read_input(day: usize, input: Option<&str>) -> Result<Vec<String>, String> {
+    match input {
+        Some(STDIN) => read_lines_from(io::stdin().lock())
+            .map_err(|e| format!("failed to read input from stdin: {}", e)),
+        Some(path) => read_lines(path),
+        None => {
+            let path = default_input(day);
+
+            if !path.exists() {
+                return Err(format!(
+                    "no input for day {} at {}, use --input <path> or --input - for stdin",
+                    day,
+                    path.display()
+                ));
+            }
+
+            read_lines(path)
+        }
+    }
+}
+
+pub fn run_part(day: &Day, part: Part, lines: Vec<String>) -> Outcome {
+    let run = day.run(part, lines);
+
+    Outcome {
+        day: day.number,
+        part,
+        status: run.answer.map_or(Status::Unsolved, Status::Solved),
+        parse_time: run.parse_time,
+        solve_time: run.solve_time,
+    }
+}
+
+/// Look up a registered day and read its puzzle input, see [`read_input`]
+pub fn load_day(day: usize, input: Option<&str>) -> Result<(&'static Day, Vec<String>), String> {
+    let day = registry::get(day).ok_or(format!("no solution for day {}", day))?;
+
+    let lines = read_input(day.number, input)?;
+
+    Ok((day, lines))
+}
+
+/**
+Run a single day. If no part is given, every part the day has a solution for is run, and it is an
+error for the day to have none. Otherwise it is an error for the day not to have a solution for the
+part.
+*/
+pub fn run_day(
+    day: usize,
+    part: Option<Part>,
+    input: Option<&str>,
+) -> Result<Vec<Outcome>, String> {
+    let (day, lines) = load_day(day, input)?;
+
+    match part {
+        Some(part) if !day.has_part(part) => Err(format!(
+            "day {} has no solution for part {}",
+            day.number, part
+        )),
+        Some(part) => Ok(vec![run_part(day, part, lines)]),
+        None if day.parts().is_empty() => Err(format!("day {} has no solutions yet", day.number)),
+        None => Ok(day
+            .parts()
+            .into_iter()
+            .map(|part| run_part(day, part, lines.clone()))
+            .collect()),
+    }
+}
+
+/**
+Run both parts of every registered day against its default input. Days without an input file, or
+with an empty one as created by `aoc new`, are reported as skipped.
+*/
+pub fn run_all() -> Vec<Outcome> {
+    let mut outcomes = vec![];
+
+    for day in DAYS {
+        let lines = read_lines(default_input(day.number))
+            .ok()
+            .filter(|lines| !lines.is_empty());
+
+        for part in Part::ALL {
+            outcomes.push(match &lines {
+                Some(lines) => run_part(day, part, lines.clone()),
+                None => Outcome {
+                    day: day.number,
+                    part,
+                    status: Status::Skipped,
+                    parse_time: Duration::ZERO,
+                    solve_time: Duration::ZERO,
+                },
+            });
+        }
+    }
+
+    outcomes
+}
+
+pub fn format_table(outcomes: &[Outcome]) -> String {
+    let answers: Vec<String> = outcomes
+        .iter()
+        .map(|outcome| match &outcome.status {
+            Status::Solved(answer) => answer.clone(),
+            Status::Unsolved => "-".to_owned(),
+            Status::Skipped => "skipped (no input)".to_owned(),
+        })
+        .collect();
+
+    let width = answers
+        .iter()
+        .map(String::len)
+        .chain(["Answer".len()])
+        .max()
+        .unwrap_or_default();
+
+    let mut table = format!(
+        "{:>3}  {:>4}  {:<width$}  {:>12}\n",
+        "Day", "Part", "Answer", "Time"
+    );
+
+    for (outcome, answer) in outcomes.iter().zip(&answers) {
+        table += &format!(
+            "{:>3}  {:>4}  {:<width$}  {:>12}\n",
+            outcome.day,
+            outcome.part.to_string(),
+            answer,
+            format!("{:.2?}", outcome.time())
+        );
+    }
+
+    let total: Duration = outcomes.iter().map(Outcome::time).sum();
+
+    table += &format!(
+        "{:<9}  {:<width$}  {:>12}\n",
+        "Total",
+        "",
+        format!("{:.2?}", total)
+    );
+
+    table
+}
+
+pub fn format_verification(checks: &[(Outcome, Verdict)]) -> String {
+    let mut table = format!(
+        "{:>3}  {:>4}  {:<7}  {}\n",
+        "Day", "Part", "Result", "Answer"
+    );
+
+    for (outcome, verdict) in checks {
+        let answer = match &outcome.status {
+            Status::Solved(answer) => answer.as_str(),
+            Status::Unsolved => "-",
+            Status::Skipped => "skipped (no input)",
+        };
+
+        let (result, detail) = match verdict {
+            Verdict::Pass => ("PASS", answer.to_owned()),
+            Verdict::Fail { expected } => ("FAIL", format!("{} (expected {})", answer, expected)),
+            Verdict::Missing => ("MISSING", answer.to_owned()),
+            Verdict::Skipped => ("SKIP", answer.to_owned()),
+        };
+
+        table += &format!(
+            "{:>3}  {:>4}  {:<7}  {}\n",
+            outcome.day,
+            outcome.part.to_string(),
+            result,
+            detail
+        );
+    }
+
+    let count = |f: fn(&Verdict) -> bool| checks.iter().filter(|(_, v)| f(v)).count();
+
+    table += &format!(
+        "{} passed, {} failed, {} missing, {} skipped\n",
+        count(|v| *v == Verdict::Pass),
+        count(|v| matches!(v, Verdict::Fail { .. })),
+        count(|v| *v == Verdict::Missing),
+        count(|v| *v == Verdict::Skipped),
+    );
+
+    table
+}
+
+fn json_string(s: &str) -> String {
+    let mut result = String::from('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            c if c.is_control() => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+
+    result.push('"');
+
+    result
+}
+
+/**
+Format an outcome as a single-line JSON object. When a verdict is given, the status is the result
+of verification instead of whether the part was solved.
+*/
+pub fn format_json(outcome: &Outcome, verdict: Option<&Verdict>) -> String {
+    let status = match (verdict, &outcome.status) {
+        (Some(Verdict::Pass), _) => "pass",
+        (Some(Verdict::Fail { .. }), _) => "fail",
+        (Some(Verdict::Missing), _) => "missing",
+        (Some(Verdict::Skipped), _) | (None, Status::Skipped) => "skipped",
+        (None, Status::Solved(_)) => "solved",
+        (None, Status::Unsolved) => "unsolved",
+    };
+
+    let answer = match &outcome.status {
+        Status::Solved(answer) => json_string(answer),
+        _ => "null".to_owned(),
+    };
+
+    let expected = match verdict {
+        Some(Verdict::Fail { expected }) => format!(",\"expected\":{}", json_string(expected)),
+        _ => String::new(),
+    };
+
+    format!(
+        "{{\"day\":{},\"part\":{},\"status\":\"{}\",\"answer\":{}{},\"parse_time_ns\":{},\"solve_time_ns\":{}}}",
+        outcome.day,
+        outcome.part,
+        status,
+        answer,
+        expected,
+        outcome.parse_time.as_nanos(),
+        outcome.solve_time.as_nanos()
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use std::{io::Cursor, time::Duration};
+
+    use super::{
+        default_input, format_json, format_table, format_verification, json_string, read_input,
+        read_lines_from, Outcome, Status,
+    };
+    use crate::answers::Verdict;
+    use crate::solution::Part;
+
+    #[test]
+    fn test_format_table() {
+        let outcomes = vec![
+            Outcome {
+                day: 1,
+                part: Part::One,
+                status: Status::Solved("1258579".to_owned()),
+                parse_time: Duration::from_millis(1),
+                solve_time: Duration::from_millis(1),
+            },
+            Outcome {
+                day: 1,
+                part: Part::Two,
+                status: Status::Unsolved,
+                parse_time: Duration::from_millis(1),
+                solve_time: Duration::ZERO,
+            },
+            Outcome {
+                day: 2,
+                part: Part::One,
+                status: Status::Skipped,
+                parse_time: Duration::ZERO,
+                solve_time: Duration::ZERO,
+            },
+        ];
+
+        let table = format_table(&outcomes);
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert_eq!(lines.len(), 5);
+        assert!(lines[1].contains("1258579"));
+        assert!(lines[2].contains(" - "));
+        assert!(lines[3].contains("skipped"));
+        assert!(lines[4].starts_with("Total") && lines[4].ends_with("3.00ms"));
+    }
+
+    #[test]
+    fn test_format_verification() {
+        let outcome = |part, answer: &str| Outcome {
+            day: 3,
+            part,
+            status: Status::Solved(answer.to_owned()),
+            parse_time: Duration::ZERO,
+            solve_time: Duration::ZERO,
+        };
+
+        let checks = vec![
+            (outcome(Part::One, "12"), Verdict::Pass),
+            (
+                outcome(Part::Two, "40"),
+                Verdict::Fail {
+                    expected: "48".to_owned(),
+                },
+            ),
+        ];
+
+        let table = format_verification(&checks);
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert_eq!(lines.len(), 4);
+        assert!(lines[1].contains("PASS") && lines[1].ends_with("12"));
+        assert!(lines[2].contains("FAIL") && lines[2].ends_with("40 (expected 48)"));
+        assert_eq!(lines[3], "1 passed, 1 failed, 0 missing, 0 skipped");
+    }
+
+    #[test]
+    fn test_read_lines_from() {
+        let lines = read_lines_from(Cursor::new("3   4\n4   3\n")).unwrap();
+
+        assert_eq!(lines, vec!["3   4".to_owned(), "4   3".to_owned()]);
+    }
+
+    #[test]
+    fn test_default_input() {
+        let path = default_input(3);
+
+        assert!(path.is_absolute());
+        assert!(path.ends_with("inputs/3.txt"));
+    }
+
+    #[test]
+    fn test_read_input_missing() {
+        let err = read_input(99, None).unwrap_err();
+
+        assert!(err.contains(&default_input(99).display().to_string()));
+        assert!(read_input(99, Some("does/not/exist.txt")).is_err());
+    }
+
+    #[test]
+    fn test_json_string() {
+        assert_eq!(json_string("1234"), "\"1234\"");
+        assert_eq!(json_string("a\"b\\c\nd"), "\"a\\\"b\\\\c\\nd\"");
+        assert_eq!(json_string("\t"), "\"\\u0009\"");
+    }
+
+    #[test]
+    fn test_format_json() {
+        let outcome = Outcome {
+            day: 7,
+            part: Part::Two,
+            status: Status::Solved("11387".to_owned()),
+            parse_time: Duration::from_nanos(1500),
+            solve_time: Duration::from_micros(20),
+        };
+
+        assert_eq!(
+            format_json(&outcome, None),
+            "{\"day\":7,\"part\":2,\"status\":\"solved\",\"answer\":\"11387\",\"parse_time_ns\":1500,\"solve_time_ns\":20000}"
+        );
+
+        assert_eq!(
+            format_json(
+                &outcome,
+                Some(&Verdict::Fail {
+                    expected: "3749".to_owned()
+                })
+            ),
+            "{\"day\":7,\"part\":2,\"status\":\"fail\",\"answer\":\"11387\",\"expected\":\"3749\",\"parse_time_ns\":1500,\"solve_time_ns\":20000}"
+        );
+
+        let skipped = Outcome {
+            status: Status::Skipped,
+            ..outcome
+        };
+
+        assert!(format_json(&skipped, None).contains("\"status\":\"skipped\",\"answer\":null"));
+    }
+}
@@ -0,0 +1,273 @@
+use std::{fs, path::Path};
+
+use regex::Regex;
+
+use crate::runner;
+
+const MODULES_PATH: &str = "src/lib.rs";
+const REGISTRY_PATH: &str = "src/registry.rs";
+
+fn template(day: usize) -> String {
+    format!(
+        r#"use crate::solution::{{Solution, Solver}};
+
+#[derive(Debug, Clone)]
+pub struct Input {{
+    lines: Vec<String>,
+}}
+
+impl From<Vec<String>> for Input {{
+    fn from(value: Vec<String>) -> Self {{
+        Self {{ lines: value }}
+    }}
+}}
+
+pub fn solution(input: Input) -> usize {{
+    todo!("day {day} part one: {{}} lines", input.lines.len())
+}}
+
+pub fn solution_part_two(input: Input) -> usize {{
+    todo!("day {day} part two: {{}} lines", input.lines.len())
+}}
+
+pub struct Problem;
+
+impl Solution for Problem {{
+    type Input = Input;
+
+    fn parse(lines: Vec<String>) -> Input {{
+        Input::from(lines)
+    }}
+
+    // Parts are reported as unsolved until these are filled in, e.g. with
+    // Some(|input| solution(input).to_string())
+    const PART_ONE: Solver<Input> = None;
+    const PART_TWO: Solver<Input> = None;
+}}
+
+#[cfg(test)]
+mod test {{
+    use super::{{solution, Input}};
+
+    const EXAMPLE: &str = "\
+";
+
+    // The answer to part one for the example
+    const EXPECTED: Option<usize> = None;
+
+    #[test]
+    fn test() {{
+        assert!(!EXAMPLE.is_empty(), "day {day} has no example yet");
+
+        let input = Input::from(EXAMPLE.lines().map(String::from).collect::<Vec<String>>());
+
+        assert_eq!(Some(solution(input)), EXPECTED);
+    }}
+}}
+"#
+    )
+}
+
+/**
+Insert `line` into a numbered list of lines in `source`, keeping the list ordered by `key` of each
+day. `pattern` must capture the day number of each existing entry in the list. Fails if the day is
+already present.
+*/
+fn insert_entry<K: Ord>(
+    source: &str,
+    pattern: &Regex,
+    day: usize,
+    line: &str,
+    key: impl Fn(usize) -> K,
+) -> Result<String, String> {
+    let mut lines: Vec<&str> = source.lines().collect();
+
+    let entries: Vec<(usize, usize)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, l)| {
+            let caps = pattern.captures(l)?;
+
+            Some((i, caps[1].parse().ok()?))
+        })
+        .collect();
+
+    if entries.iter().any(|&(_, number)| number == day) {
+        return Err(format!("day {} is already registered", day));
+    }
+
+    let position = entries
+        .iter()
+        .find(|&&(_, number)| key(number) > key(day))
+        .map(|&(i, _)| i)
+        .or(entries.last().map(|&(i, _)| i + 1))
+        .ok_or("couldn't find where to register the day")?;
+
+    lines.insert(position, line);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+fn register_module(source: &str, day: usize) -> Result<String, String> {
+    let pattern = Regex::new(r"^(?://\s*)?pub mod problem(\d+);").unwrap();
+
+    // rustfmt sorts module declarations by name, so problem10 comes before problem2
+    insert_entry(
+        source,
+        &pattern,
+        day,
+        &format!("pub mod problem{};", day),
+        |number| format!("problem{}", number),
+    )
+}
+
+fn register_day(source: &str, day: usize) -> Result<String, String> {
+    let pattern = Regex::new(r"^\s*Day::new::<crate::problem(\d+)::Problem>").unwrap();
+
+    insert_entry(
+        source,
+        &pattern,
+        day,
+        &format!("    Day::new::<crate::problem{}::Problem>({}),", day, day),
+        |number| number,
+    )
+}
+
+fn write_new(path: &Path, contents: &str) -> Result<(), String> {
+    fs::write(path, contents).map_err(|e| format!("failed to write {}: {}", path.display(), e))
+}
+
+fn update(path: &Path, edit: impl FnOnce(&str) -> Result<String, String>) -> Result<(), String> {
+    let source = fs::read_to_string(path)
+        .map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+
+    write_new(path, &edit(&source)?)
+}
+
+/**
+Create the module and an empty input file for a new day, and register the day with the runner.
+Nothing is written if any of the files already exist or the day is already registered.
+*/
+pub fn scaffold(day: usize) -> Result<(), String> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("invalid day {}, expected 1 to 25", day));
+    }
+
+    let module = runner::crate_path(format!("src/problem{}.rs", day));
+    let input = runner::default_input(day);
+
+    for path in [&module, &input] {
+        if path.exists() {
+            return Err(format!("{} already exists", path.display()));
+        }
+    }
+
+    let modules_path = runner::crate_path(MODULES_PATH);
+    let registry_path = runner::crate_path(REGISTRY_PATH);
+
+    // Check both edits apply before writing anything
+    for (path, edit) in [
+        (&modules_path, register_module as fn(&str, usize) -> _),
+        (&registry_path, register_day),
+    ] {
+        let source = fs::read_to_string(path)
+            .map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+
+        edit(&source, day)?;
+    }
+
+    write_new(&module, &template(day))?;
+    write_new(&input, "")?;
+    update(&modules_path, |source| register_module(source, day))?;
+    update(&registry_path, |source| register_day(source, day))?;
+
+    println!("Created {} and {}", module.display(), input.display());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{register_day, register_module, template};
+
+    const MODULES: &str = "\
+pub mod common;
+
+pub mod problem1;
+pub mod problem10;
+// pub mod problem2;
+pub mod problem4;
+
+fn main() {}
+";
+
+    const REGISTRY: &str = "\
+pub const DAYS: &[Day] = &[
+    Day::new::<crate::problem1::Problem>(1),
+    Day::new::<crate::problem4::Problem>(4),
+];
+";
+
+    #[test]
+    fn test_register_module() {
+        let result = register_module(MODULES, 3).unwrap();
+
+        assert!(result.contains("// pub mod problem2;\npub mod problem3;\npub mod problem4;\n"));
+
+        let result = register_module(MODULES, 12).unwrap();
+
+        assert!(result.contains("pub mod problem10;\npub mod problem12;\n// pub mod problem2;"));
+
+        let result = register_module(MODULES, 5).unwrap();
+
+        assert!(result.contains("pub mod problem4;\npub mod problem5;\n\nfn main"));
+
+        assert!(register_module(MODULES, 1).is_err());
+    }
+
+    #[test]
+    fn test_register_in_crate() {
+        let modules = register_module(include_str!("lib.rs"), 16).unwrap();
+
+        assert!(modules.contains("pub mod problem15;\npub mod problem16;\npub mod problem2;\n"));
+
+        // The declarations have to stay in the order rustfmt puts them in
+        let names: Vec<&str> = modules
+            .lines()
+            .filter_map(|line| line.strip_prefix("pub mod ")?.strip_suffix(';'))
+            .filter(|name| name.starts_with("problem"))
+            .collect();
+        assert!(names.windows(2).all(|w| w[0] < w[1]));
+
+        let registry = register_day(include_str!("registry.rs"), 16).unwrap();
+
+        assert!(registry.contains("(15),\n    Day::new::<crate::problem16::Problem>(16),\n];"));
+    }
+
+    #[test]
+    fn test_register_day() {
+        let result = register_day(REGISTRY, 2).unwrap();
+
+        assert!(result.contains(
+            "(1),\n    Day::new::<crate::problem2::Problem>(2),\n    Day::new::<crate::problem4"
+        ));
+
+        let result = register_day(REGISTRY, 5).unwrap();
+
+        assert!(result.contains("(4),\n    Day::new::<crate::problem5::Problem>(5),\n];"));
+
+        assert!(register_day(REGISTRY, 4).is_err());
+    }
+
+    #[test]
+    fn test_template() {
+        let source = template(16);
+
+        assert!(source.contains("impl From<Vec<String>> for Input"));
+        assert!(source.contains("pub fn solution_part_two(input: Input) -> usize"));
+        assert!(source.contains("const PART_ONE: Solver<Input> = None;"));
+        assert!(source.contains("const PART_TWO: Solver<Input> = None;"));
+        assert!(source.contains("const EXAMPLE: &str"));
+        assert!(source.contains("const EXPECTED: Option<usize> = None;"));
+    }
+}
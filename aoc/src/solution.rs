@@ -0,0 +1,101 @@
+use std::fmt::Display;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/**
+Every answer is reported as a string so that days with different result types can be run, printed
+and compared in the same way.
+*/
+pub type Answer = String;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl TryFrom<&str> for Part {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!("invalid part '{}', expected 1 or 2", value)),
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => f.write_str("1"),
+            Part::Two => f.write_str("2"),
+        }
+    }
+}
+
+/**
+Solves one part of a day from its parsed input, or `None` if the part hasn't been solved yet. Days
+give one for each part, so whether a part has a solution can be checked without running it.
+*/
+pub type Solver<I> = Option<fn(I) -> Answer>;
+
+pub trait Solution {
+    type Input;
+
+    const PART_ONE: Solver<Self::Input>;
+
+    /// Days that don't have a solution for part two yet can leave this out
+    const PART_TWO: Solver<Self::Input> = None;
+
+    fn parse(lines: Vec<String>) -> Self::Input;
+
+    /// The solver for the given part
+    fn solver(part: Part) -> Solver<Self::Input> {
+        match part {
+            Part::One => Self::PART_ONE,
+            Part::Two => Self::PART_TWO,
+        }
+    }
+}
+
+static DEBUG_OUTPUT: AtomicBool = AtomicBool::new(true);
+
+/// Turn solutions' [`debug!`](crate::debug) output on or off, e.g. so benchmarks aren't flooded
+pub fn set_debug_output(enabled: bool) {
+    DEBUG_OUTPUT.store(enabled, Ordering::Relaxed);
+}
+
+pub fn debug_output() -> bool {
+    DEBUG_OUTPUT.load(Ordering::Relaxed)
+}
+
+/**
+Print something a solution wants to show along the way, like a picture of the grid. It goes to
+stderr so that it can't be mixed up with the answers, and is dropped while debug output is off.
+*/
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        if $crate::solution::debug_output() {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::Part;
+
+    #[test]
+    fn test_part_from_str() {
+        assert_eq!(Part::try_from("1"), Ok(Part::One));
+        assert_eq!(Part::try_from("2"), Ok(Part::Two));
+        assert!(Part::try_from("3").is_err());
+        assert!(Part::try_from("one").is_err());
+    }
+}
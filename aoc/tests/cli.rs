@@ -0,0 +1,68 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Run the binary with the given arguments and input on stdin, returning what it wrote to stdout
+fn run(args: &[&str], input: &str) -> String {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn test_json_output_is_only_json() {
+    let trails = "\
+89010123
+78121874
+87430965
+96549874
+45678903
+32019012
+01329801
+10456732
+";
+
+    let antennas = "\
+............
+........0...
+.....0......
+.......0....
+....0.......
+......A.....
+............
+............
+........A...
+.........A..
+............
+............
+";
+
+    for (day, input, answers) in [("10", trails, ["36", "81"]), ("8", antennas, ["14", "34"])] {
+        let stdout = run(&[day, "--input", "-", "--format", "json"], input);
+
+        assert_eq!(stdout.lines().count(), answers.len());
+
+        for (line, answer) in stdout.lines().zip(answers) {
+            let outcome: serde_json::Value = serde_json::from_str(line)
+                .unwrap_or_else(|e| panic!("day {} printed {:?}: {}", day, line, e));
+
+            assert_eq!(outcome["day"], day.parse::<u64>().unwrap());
+            assert_eq!(outcome["answer"], answer);
+        }
+    }
+}
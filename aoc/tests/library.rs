@@ -0,0 +1,24 @@
+use aoc::common::{Grid, Vector2di};
+use aoc::registry;
+use aoc::solution::Part;
+
+#[test]
+fn test_grid_from_outside_the_crate() {
+    let grid = Grid::try_from(vec![vec!['a', 'b', 'c'], vec!['d', 'e', 'f']]).unwrap();
+
+    assert_eq!((grid.width(), grid.height()), (3, 2));
+    assert_eq!(grid.get(Vector2di::new(1, 1)), Some(&'e'));
+    assert!(grid.get(Vector2di::new(0, 2)).is_none());
+}
+
+#[test]
+fn test_run_registered_day() {
+    let day = registry::get(1).unwrap();
+
+    let lines = ["3   4", "4   3", "2   5", "1   3", "3   9", "3   3"]
+        .iter()
+        .map(|line| line.to_string())
+        .collect();
+
+    assert_eq!(day.run(Part::One, lines).answer, Some("11".to_owned()));
+}